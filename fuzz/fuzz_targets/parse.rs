@@ -0,0 +1,27 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use steamid_ng::SteamID;
+
+// None of the parsers may panic, whatever they're fed
+fuzz_target!(|input: &str| {
+    let _ = SteamID::from_steam2(input);
+    let _ = SteamID::from_steam3(input);
+    let _ = input.parse::<SteamID>();
+    let _ = SteamID::from_lobby_connect(input);
+    let _ = SteamID::from_friend_code(input);
+    let _ = SteamID::from_invite_code(input);
+    let _ = SteamID::from_community_url(input);
+    let _ = SteamID::from_group_url(input);
+    let _ = SteamID::from_steam_url(input);
+    let _ = SteamID::from_trade_url(input);
+    let _ = SteamID::from_steam_hex(input);
+    let _ = SteamID::from_steam3_bare(input);
+    let _ = SteamID::find_all(input).count();
+    let _ = steamid_ng::status::parse(input);
+    let _ = steamid_ng::bans::parse_banned_user_cfg(input);
+    let _ = steamid_ng::vdf::find_steamids(input);
+    let _ = SteamID::from_steam2_strict(input);
+    let _ = SteamID::from_steam2_extended(input);
+    let _ = SteamID::parse_with(input, steamid_ng::ParseOptions::lenient());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use steamid_ng::SteamID;
+
+// The byte parsers see input that isn't UTF-8, which the str parsers never do
+fuzz_target!(|input: &[u8]| {
+    let _ = SteamID::from_steam2_bytes(input);
+    let _ = SteamID::from_steam3_bytes(input);
+});
@@ -0,0 +1,37 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use steamid_ng::{AccountType, SteamID};
+
+fuzz_target!(|steam64: u64| {
+    let id = SteamID::from(steam64);
+
+    // Rendering must never panic, and whatever steam3 produces has to parse again
+    let steam3 = id.steam3();
+    let reparsed = SteamID::from_steam3(&steam3)
+        .unwrap_or_else(|_| panic!("{} rendered as unparseable {}", steam64, steam3));
+    assert_eq!(reparsed.account_id(), id.account_id());
+
+    // Individual accounts in a real universe survive steam2 exactly, modulo the instance
+    let steam2 = id.steam2();
+    if id.account_type() == AccountType::Individual && steam2.starts_with("STEAM_") {
+        let reparsed = SteamID::from_steam2(&steam2)
+            .unwrap_or_else(|_| panic!("{} rendered as unparseable {}", steam64, steam2));
+        assert_eq!(reparsed.account_id(), id.account_id());
+    }
+
+    // Friend and invite codes only keep the account id
+    if let Some(code) = id.friend_code() {
+        let decoded = SteamID::from_friend_code(&code)
+            .unwrap_or_else(|_| panic!("{} rendered as undecodable {}", steam64, code));
+        assert_eq!(decoded.account_id(), id.account_id());
+    }
+    if let Some(code) = id.invite_code() {
+        let decoded = SteamID::from_invite_code(&code)
+            .unwrap_or_else(|_| panic!("{} rendered as undecodable {}", steam64, code));
+        assert_eq!(decoded.account_id(), id.account_id());
+    }
+
+    // steam64 always round-trips through FromStr
+    assert_eq!(u64::from(id).to_string().parse::<SteamID>(), Ok(id));
+});
@@ -0,0 +1,36 @@
+use crate::{AccountType, Instance, SteamID, Universe};
+
+impl SteamID {
+    /// The blank anonymous user ID that clients log on to a CM with before they have an account,
+    /// SteamKit's `CreateBlankAnonUserLogon`.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, Universe};
+    /// let s = SteamID::anon_user(Universe::Public);
+    /// assert_eq!(s.steam3(), "[a:1:0]");
+    /// assert!(s.is_blank_anon_account());
+    /// ```
+    pub fn anon_user(universe: Universe) -> Self {
+        Self::new(0, Instance::All, AccountType::AnonUser, universe)
+    }
+
+    /// The blank anonymous game server ID that game servers log on with when they don't have a
+    /// login token, SteamKit's `CreateBlankAnonLogon`. Steam assigns the actual ID in response.
+    pub fn anon_game_server(universe: Universe) -> Self {
+        Self::new(0, Instance::All, AccountType::AnonGameServer, universe)
+    }
+
+    /// Whether this is an anonymous user or anonymous game server.
+    pub fn is_anonymous(&self) -> bool {
+        matches!(
+            self.account_type(),
+            AccountType::AnonUser | AccountType::AnonGameServer
+        )
+    }
+
+    /// Whether this is an anonymous account with neither an account id nor an instance yet, as
+    /// [`SteamID::anon_user`] and [`SteamID::anon_game_server`] make.
+    pub fn is_blank_anon_account(&self) -> bool {
+        self.account_id() == 0 && self.is_anonymous() && self.instance_value() == 0
+    }
+}
@@ -0,0 +1,105 @@
+//! Reading and writing Source engine ban lists: `banid` console commands and the
+//! `banned_user.cfg` files `writeid` saves them to.
+//!
+//! ```text
+//! banid 0 STEAM_0:0:11101
+//! banid 1440 STEAM_0:1:11101 kick
+//! ```
+//!
+//! The duration is in minutes, with 0 meaning permanent. `banid` also takes the userid of a
+//! connected player in place of the steam2 ID, but since that can't be turned into a SteamID,
+//! such lines are rejected.
+//!
+//! To send bans to a running server instead, see the [`commands`](crate::commands) module.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use crate::{commands, Steam2RenderError, SteamID};
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Ban {
+    pub steamid: SteamID,
+    /// `None` for permanent bans.
+    pub duration: Option<Duration>,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BanFileError {
+    line: usize,
+}
+
+impl BanFileError {
+    /// The (1-based) line of the input that couldn't be parsed.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Error for BanFileError {}
+
+impl Display for BanFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Malformed banid command on line {}", self.line)
+    }
+}
+
+/// Parses a single `banid <minutes> <steamid> [kick]` command. Steam3 IDs are accepted as well
+/// as steam2 IDs, and the minutes may be fractional.
+pub fn parse_banid(command: &str) -> Result<Ban, BanFileError> {
+    parse_line(command).ok_or(BanFileError { line: 1 })
+}
+
+/// Parses a `banned_user.cfg`, one `banid` command per line. Blank lines and `//` comments are
+/// skipped.
+pub fn parse_banned_user_cfg(input: &str) -> Result<Vec<Ban>, BanFileError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with("//")
+        })
+        .map(|(i, line)| parse_line(line).ok_or(BanFileError { line: i + 1 }))
+        .collect()
+}
+
+/// Writes `bans` the way `writeid` does, with steam2 IDs in universe 0. Durations are rounded
+/// up to whole minutes. Fails if any of them isn't for an individual.
+pub fn to_banned_user_cfg(bans: &[Ban]) -> Result<String, Steam2RenderError> {
+    let mut cfg = String::new();
+    for ban in bans {
+        cfg.push_str(&commands::banid(ban, false)?);
+        cfg.push('\n');
+    }
+    Ok(cfg)
+}
+
+fn parse_line(line: &str) -> Option<Ban> {
+    let mut words = line.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case("banid") {
+        return None;
+    }
+
+    let minutes: f64 = words.next()?.parse().ok()?;
+    // Rules out negative, infinite and NaN durations, and anything too long for a Duration
+    let duration = if minutes == 0.0 {
+        None
+    } else {
+        Some(Duration::try_from_secs_f64(minutes * 60.0).ok()?)
+    };
+    let uniqueid = words.next()?.trim_matches('"');
+    let steamid = SteamID::from_steam2(uniqueid)
+        .or_else(|_| SteamID::from_steam3(uniqueid))
+        .ok()?;
+    match words.next() {
+        None => (),
+        Some(kick) if kick.eq_ignore_ascii_case("kick") && words.next().is_none() => (),
+        Some(_) => return None,
+    }
+
+    Some(Ban { steamid, duration })
+}
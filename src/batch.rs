@@ -0,0 +1,34 @@
+//! Parsing many IDs at once, see [`SteamID::parse_many`].
+
+use crate::{SteamID, SteamIDParseError};
+
+impl SteamID {
+    /// Parses every input the way `FromStr` does, keeping each one's result. Nothing is
+    /// allocated per input, good or bad.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let parsed = SteamID::parse_many(["76561197960287930", "[U:1:22202]", "nope"]);
+    /// assert_eq!(parsed[0], parsed[1]);
+    /// assert!(parsed[2].is_err());
+    /// ```
+    pub fn parse_many<'a, I>(inputs: I) -> Vec<Result<SteamID, SteamIDParseError>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        inputs.into_iter().map(SteamID::parse_any).collect()
+    }
+
+    /// Parses every input, stopping at the first that doesn't parse. Its index is returned
+    /// along with the error.
+    pub fn try_parse_many<'a, I>(inputs: I) -> Result<Vec<SteamID>, (usize, SteamIDParseError)>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        inputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, input)| SteamID::parse_any(input).map_err(|e| (i, e)))
+            .collect()
+    }
+}
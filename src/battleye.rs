@@ -0,0 +1,29 @@
+//! BattlEye GUIDs, which Arma and DayZ servers list players and bans by.
+//!
+//! A GUID is the MD5 hash of `BE` followed by the little-endian steam64, so it can be computed
+//! from a SteamID but not the other way around. To find the account behind a GUID, compare it
+//! against the SteamIDs you know of.
+
+use crate::SteamID;
+
+impl SteamID {
+    /// The BattlEye GUID of this SteamID, as 32 lowercase hex digits.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let guid = SteamID::from(76561197960287930).battleye_guid();
+    /// assert_eq!(guid, "a357f31c8335a5263e0d816e64445b6a");
+    /// ```
+    pub fn battleye_guid(&self) -> String {
+        let mut input = [0; 10];
+        input[..2].copy_from_slice(b"BE");
+        input[2..].copy_from_slice(&self.0.to_le_bytes());
+        format!("{:x}", md5::compute(input))
+    }
+
+    /// Whether `guid` is the BattlEye GUID of this SteamID. Admin tools print GUIDs in either
+    /// case, so the comparison ignores it.
+    pub fn matches_battleye_guid(&self, guid: &str) -> bool {
+        self.battleye_guid().eq_ignore_ascii_case(guid)
+    }
+}
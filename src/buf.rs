@@ -0,0 +1,32 @@
+use bytes::{Buf, BufMut, TryGetError};
+
+use crate::SteamID;
+
+/// Reads SteamIDs from a [`Buf`], as the fixed64 little-endian steam64 Steam's own protocols use.
+pub trait SteamIDBufExt: Buf {
+    /// # Panics
+    ///
+    /// If fewer than 8 bytes remain, same as [`Buf::get_u64_le`].
+    fn get_steamid(&mut self) -> SteamID {
+        SteamID::from(self.get_u64_le())
+    }
+
+    fn try_get_steamid(&mut self) -> Result<SteamID, TryGetError> {
+        self.try_get_u64_le().map(SteamID::from)
+    }
+}
+
+impl<B: Buf + ?Sized> SteamIDBufExt for B {}
+
+/// Writes SteamIDs to a [`BufMut`], as the fixed64 little-endian steam64 Steam's own protocols
+/// use.
+pub trait SteamIDBufMutExt: BufMut {
+    /// # Panics
+    ///
+    /// If less than 8 bytes of capacity remain, same as [`BufMut::put_u64_le`].
+    fn put_steamid(&mut self, steamid: SteamID) {
+        self.put_u64_le(steamid.into())
+    }
+}
+
+impl<B: BufMut + ?Sized> SteamIDBufMutExt for B {}
@@ -0,0 +1,81 @@
+use crate::{AccountType, Instance, SteamID, SteamIDParseError, Universe};
+
+/// Builds a SteamID field by field, see [`SteamID::builder`]. Unset fields default to an
+/// individual in the public universe with account id 0, and the instance to whichever one the
+/// account type normally has: desktop for individuals, console for console users and none for
+/// everything else.
+///
+/// ```
+/// # use steamid_ng::{AccountType, SteamID, Universe};
+/// let clan = SteamID::builder()
+///     .account_id(4)
+///     .account_type(AccountType::Clan)
+///     .universe(Universe::Public)
+///     .build()
+///     .unwrap();
+/// assert_eq!(clan.steam3(), "[g:1:4]");
+///
+/// let gabe = SteamID::builder().account_id(22202).build().unwrap();
+/// assert_eq!(gabe, SteamID::from(76561197960287930));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SteamIDBuilder {
+    account_id: u32,
+    instance: Option<Instance>,
+    account_type: AccountType,
+    universe: Universe,
+}
+
+impl Default for SteamIDBuilder {
+    fn default() -> Self {
+        SteamIDBuilder {
+            account_id: 0,
+            instance: None,
+            account_type: AccountType::Individual,
+            universe: Universe::Public,
+        }
+    }
+}
+
+impl SteamIDBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn account_id(mut self, account_id: u32) -> Self {
+        self.account_id = account_id;
+        self
+    }
+
+    pub fn instance(mut self, instance: Instance) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    pub fn account_type(mut self, account_type: AccountType) -> Self {
+        self.account_type = account_type;
+        self
+    }
+
+    pub fn universe(mut self, universe: Universe) -> Self {
+        self.universe = universe;
+        self
+    }
+
+    /// Puts the ID together, rejecting it if it isn't [valid](SteamID::is_valid), as
+    /// [`SteamID::try_new`] does.
+    pub fn build(&self) -> Result<SteamID, SteamIDParseError> {
+        let instance = self.instance.unwrap_or(match self.account_type {
+            AccountType::Individual => Instance::Desktop,
+            AccountType::ConsoleUser => Instance::Console,
+            _ => Instance::All,
+        });
+        SteamID::try_new(self.account_id, instance, self.account_type, self.universe)
+    }
+}
+
+impl SteamID {
+    pub fn builder() -> SteamIDBuilder {
+        SteamIDBuilder::new()
+    }
+}
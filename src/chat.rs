@@ -0,0 +1,217 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+use serde::{
+    de::{self, Unexpected},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{AccountType, Instance, SteamID};
+
+/// The flag bits of an instance. [`Instance`] names a single value, but the field is a bitmask,
+/// so matchmaking lobbies for instance carry both [`InstanceFlags::LOBBY`] and
+/// [`InstanceFlags::MMS_LOBBY`]. Serializes as the raw bits.
+///
+/// ```
+/// # use steamid_ng::{InstanceFlags, SteamID};
+/// let lobby = SteamID::from_steam3("[T:1:100614564:393216]").unwrap();
+/// let flags = lobby.instance_flags();
+/// assert!(flags.contains(InstanceFlags::LOBBY.union(InstanceFlags::MMS_LOBBY)));
+/// assert!(!flags.contains(InstanceFlags::CLAN));
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug, Serialize)]
+#[serde(transparent)]
+pub struct InstanceFlags(u32);
+
+impl InstanceFlags {
+    /// `k_unSteamUserDesktopInstance`.
+    pub const DESKTOP: Self = Self(Instance::Desktop as u32);
+    /// `k_unSteamUserConsoleInstance`.
+    pub const CONSOLE: Self = Self(Instance::Console as u32);
+    /// `k_unSteamUserWebInstance`.
+    pub const WEB: Self = Self(Instance::Web as u32);
+    /// [`SteamID::CHAT_INSTANCE_FLAG_CLAN`].
+    pub const CLAN: Self = Self(SteamID::CHAT_INSTANCE_FLAG_CLAN);
+    /// [`SteamID::CHAT_INSTANCE_FLAG_LOBBY`].
+    pub const LOBBY: Self = Self(SteamID::CHAT_INSTANCE_FLAG_LOBBY);
+    /// [`SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY`].
+    pub const MMS_LOBBY: Self = Self(SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY);
+    /// Every known flag.
+    pub const ALL: Self = Self(
+        Self::DESKTOP.0
+            | Self::CONSOLE.0
+            | Self::WEB.0
+            | Self::CLAN.0
+            | Self::LOBBY.0
+            | Self::MMS_LOBBY.0,
+    );
+
+    /// No flags set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The flags in `bits`, or `None` if it has bits set that aren't a known flag.
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits & !Self::ALL.0 == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// The flags in `bits`, ignoring any bits that aren't a known flag.
+    pub const fn from_bits_truncate(bits: u32) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every flag in `other` is also set here.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether any flag in `other` is also set here.
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// The flags set in either.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The flags set in both.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for InstanceFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for InstanceFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+impl BitAnd for InstanceFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl<'de> Deserialize<'de> for InstanceFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = u32::deserialize(deserializer)?;
+        Self::from_bits(bits).ok_or_else(|| {
+            de::Error::invalid_value(
+                Unexpected::Unsigned(bits.into()),
+                &"a combination of instance flags",
+            )
+        })
+    }
+}
+
+impl SteamID {
+    /// The part of a chat's instance that isn't flags, `k_EChatAccountInstanceMask`.
+    pub const CHAT_ACCOUNT_INSTANCE_MASK: u32 = 0x0000_0FFF;
+    /// Set on the chat rooms of groups, `k_EChatInstanceFlagClan`.
+    pub const CHAT_INSTANCE_FLAG_CLAN: u32 = Instance::FlagClan as u32;
+    /// Set on lobbies, `k_EChatInstanceFlagLobby`.
+    pub const CHAT_INSTANCE_FLAG_LOBBY: u32 = Instance::FlagLobby as u32;
+    /// Set on lobbies created through matchmaking, `k_EChatInstanceFlagMMSLobby`.
+    pub const CHAT_INSTANCE_FLAG_MMS_LOBBY: u32 = Instance::FlagMMSLobby as u32;
+
+    /// The instance with the chat flags cleared.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let lobby = SteamID::CHAT_INSTANCE_FLAG_LOBBY | SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY;
+    /// assert_eq!(SteamID::strip_chat_flags(lobby | 1), 1);
+    /// ```
+    pub const fn strip_chat_flags(instance: u32) -> u32 {
+        instance & Self::CHAT_ACCOUNT_INSTANCE_MASK
+    }
+
+    /// The chat flags set in the instance, as a combination of the `CHAT_INSTANCE_FLAG_*`
+    /// constants. [`SteamID::instance`] can only report one of them at a time.
+    pub fn chat_instance_flags(&self) -> u32 {
+        self.instance_value() & Self::ACCOUNT_INSTANCE_MASK & !Self::CHAT_ACCOUNT_INSTANCE_MASK
+    }
+
+    /// The flags set in the instance. Chats only report their chat flags, since the rest of
+    /// their instance is [`SteamID::CHAT_ACCOUNT_INSTANCE_MASK`]; everything else reports the
+    /// known bits of [`SteamID::instance_value`], so combined values read back whole.
+    pub fn instance_flags(&self) -> InstanceFlags {
+        if self.is_chat() {
+            InstanceFlags::from_bits_truncate(self.chat_instance_flags())
+        } else {
+            InstanceFlags::from_bits_truncate(self.instance_value())
+        }
+    }
+
+    /// Whether this is a chat room of any kind, including lobbies and group chats.
+    pub fn is_chat(&self) -> bool {
+        self.account_type() == AccountType::Chat
+    }
+
+    /// Whether this is a chat with the lobby flag set, matchmaking lobbies included.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// assert!(SteamID::from_steam3("[L:1:100614564]").unwrap().is_lobby());
+    /// assert!(!SteamID::from_steam3("[c:1:4]").unwrap().is_lobby());
+    /// ```
+    pub fn is_lobby(&self) -> bool {
+        self.is_chat() && self.chat_instance_flags() & Self::CHAT_INSTANCE_FLAG_LOBBY != 0
+    }
+
+    /// The SteamID of a group's chat room, `[c:1:x]` for the group `[g:1:x]`, or `None` if this
+    /// isn't a clan. This is SteamKit's `ClanIDToChatID`.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let clan = SteamID::from_steam3("[g:1:4]").unwrap();
+    /// let chat = clan.to_clan_chat().unwrap();
+    /// assert_eq!(chat.steam3(), "[c:1:4]");
+    /// assert_eq!(chat.chat_to_clan(), Some(clan));
+    /// ```
+    pub fn to_clan_chat(&self) -> Option<SteamID> {
+        if !self.is_clan() {
+            return None;
+        }
+
+        let mut chat = *self;
+        chat.set_account_type(AccountType::Chat);
+        chat.set_instance(Instance::FlagClan);
+        Some(chat)
+    }
+
+    /// The group a clan chat room belongs to, or `None` if this isn't a chat with the clan flag
+    /// set. The inverse of [`SteamID::to_clan_chat`], and SteamKit's `ChatIDToClanID`.
+    pub fn chat_to_clan(&self) -> Option<SteamID> {
+        if !self.is_chat() || self.chat_instance_flags() & Self::CHAT_INSTANCE_FLAG_CLAN == 0 {
+            return None;
+        }
+
+        let mut clan = *self;
+        clan.set_account_type(AccountType::Clan);
+        clan.set_instance(Instance::All);
+        Some(clan)
+    }
+}
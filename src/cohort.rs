@@ -0,0 +1,74 @@
+//! A rough heuristic for guessing when an individual account was registered.
+//!
+//! Account ids are handed out more or less sequentially, so the account id of a user says
+//! something about when the account was created. The mapping is far from exact: the growth rate
+//! varied wildly over the years, and the default table below is an approximation assembled from
+//! publicly observed profiles. Treat the result as a hint for analytics, never as a fact about a
+//! specific account. If you have better data, pass your own table to
+//! [`SteamID::estimated_signup_cohort_with`].
+
+use crate::{AccountType, SteamID};
+
+/// Accounts with an account id of at least `first_account_id` (and less than the next entry's)
+/// are assumed to have been registered in `year`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SignupCohort {
+    pub first_account_id: u32,
+    pub year: u16,
+}
+
+const fn cohort(first_account_id: u32, year: u16) -> SignupCohort {
+    SignupCohort {
+        first_account_id,
+        year,
+    }
+}
+
+/// The approximate first account id of each year, in ascending order.
+pub const DEFAULT_SIGNUP_COHORTS: &[SignupCohort] = &[
+    cohort(1, 2003),
+    cohort(3_000_000, 2004),
+    cohort(9_700_000, 2005),
+    cohort(19_700_000, 2006),
+    cohort(25_000_000, 2007),
+    cohort(30_000_000, 2008),
+    cohort(40_000_000, 2009),
+    cohort(55_000_000, 2010),
+    cohort(75_000_000, 2011),
+    cohort(95_000_000, 2012),
+    cohort(120_000_000, 2013),
+    cohort(165_000_000, 2014),
+    cohort(210_000_000, 2015),
+    cohort(260_000_000, 2016),
+    cohort(330_000_000, 2017),
+    cohort(420_000_000, 2018),
+    cohort(880_000_000, 2019),
+    cohort(1_000_000_000, 2020),
+    cohort(1_100_000_000, 2021),
+    cohort(1_230_000_000, 2022),
+    cohort(1_350_000_000, 2023),
+    cohort(1_480_000_000, 2024),
+    cohort(1_610_000_000, 2025),
+];
+
+impl SteamID {
+    /// Estimates the registration year of an individual account using
+    /// [`DEFAULT_SIGNUP_COHORTS`]. See the [`cohort`](crate::cohort) module for caveats.
+    ///
+    /// Returns `None` for anything that isn't an individual account with a non-zero account id.
+    pub fn estimated_signup_cohort(&self) -> Option<u16> {
+        self.estimated_signup_cohort_with(DEFAULT_SIGNUP_COHORTS)
+    }
+
+    /// Like [`SteamID::estimated_signup_cohort`], but with a caller-provided table. The table
+    /// must be sorted by `first_account_id`.
+    pub fn estimated_signup_cohort_with(&self, cohorts: &[SignupCohort]) -> Option<u16> {
+        if self.account_type() != AccountType::Individual {
+            return None;
+        }
+
+        let account_id = self.account_id_non_zero()?.get();
+        let index = cohorts.partition_point(|c| c.first_account_id <= account_id);
+        index.checked_sub(1).map(|i| cohorts[i].year)
+    }
+}
@@ -0,0 +1,74 @@
+//! Rendering ban commands to send over RCON or paste into a server console, the counterpart of
+//! the [`bans`](crate::bans) parsers.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use steamid_ng::{bans::Ban, commands, SteamID};
+//! let ban = Ban {
+//!     steamid: SteamID::from(76561197960287930),
+//!     duration: Some(Duration::from_secs(24 * 60 * 60)),
+//! };
+//! assert_eq!(commands::banid(&ban, true).unwrap(), "banid 1440 STEAM_0:0:11101 kick");
+//! assert_eq!(
+//!     commands::sm_addban(&ban, "Aimbot").unwrap(),
+//!     r#"sm_addban 1440 "STEAM_0:0:11101" "Aimbot""#
+//! );
+//! ```
+//!
+//! Durations are rounded up to whole minutes, so even the shortest ban lasts a minute, and
+//! permanent bans are written as 0. Steam2 IDs are in universe 0, as Source servers and
+//! SourceMod print them. Only individuals have a steam2 ID, so the commands can't be written for
+//! any other account type.
+
+use std::time::Duration;
+
+use crate::{bans::Ban, sourcebans::authid, Steam2RenderError};
+
+/// The Source and GoldSrc engine's `banid <minutes> <steamid>`, with `kick` appended if the
+/// player should be kicked too.
+pub fn banid(ban: &Ban, kick: bool) -> Result<String, Steam2RenderError> {
+    let mut command = format!(
+        "banid {} {}",
+        minutes(ban.duration),
+        authid::try_render(ban.steamid)?
+    );
+    if kick {
+        command.push_str(" kick");
+    }
+    Ok(command)
+}
+
+/// SourceMod's `sm_ban`, which bans a player who is connected, targeting them by SteamID.
+pub fn sm_ban(ban: &Ban, reason: &str) -> Result<String, Steam2RenderError> {
+    Ok(format!(
+        "sm_ban \"#{}\" {} \"{}\"",
+        authid::try_render(ban.steamid)?,
+        minutes(ban.duration),
+        sanitize(reason)
+    ))
+}
+
+/// SourceMod's `sm_addban`, which bans a SteamID whether or not they're connected.
+pub fn sm_addban(ban: &Ban, reason: &str) -> Result<String, Steam2RenderError> {
+    Ok(format!(
+        "sm_addban {} \"{}\" \"{}\"",
+        minutes(ban.duration),
+        authid::try_render(ban.steamid)?,
+        sanitize(reason)
+    ))
+}
+
+// Never 0 for a timed ban, since that would make it permanent
+fn minutes(duration: Option<Duration>) -> u64 {
+    const MINUTE_NANOS: u128 = 60_000_000_000;
+    duration.map_or(0, |d| (d.as_nanos().div_ceil(MINUTE_NANOS) as u64).max(1))
+}
+
+// The console has no escapes: a quote would end the reason early, and a semicolon or line break
+// would start another command
+fn sanitize(reason: &str) -> String {
+    reason
+        .chars()
+        .filter(|c| !matches!(c, '"' | ';' | '\n' | '\r'))
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! steamcommunity.com profile, group and trade offer URLs.
+
+use crate::{
+    AccountType, Cursor, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe,
+};
+
+const COMMUNITY_HOST: &str = "steamcommunity.com/";
+
+impl SteamID {
+    /// The canonical `https://steamcommunity.com/profiles/<steam64>` URL.
+    pub fn community_url(&self) -> String {
+        format!("https://steamcommunity.com/profiles/{}", self.0)
+    }
+
+    /// Parses a `steamcommunity.com/profiles/...` URL. The scheme and `www.` are optional, and
+    /// anything after the ID (a trailing slash, subpages, query parameters) is ignored. Like
+    /// Steam, this accepts steam3 IDs in place of the steam64.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from_community_url("https://steamcommunity.com/profiles/76561197960287930/?l=english");
+    /// assert_eq!(s, Ok(SteamID::from(76561197960287930)));
+    /// ```
+    pub fn from_community_url(url: &str) -> Result<Self, SteamIDParseError> {
+        community_id(url, "profiles/").map(|(steamid, _)| steamid)
+    }
+
+    /// The `https://steamcommunity.com/gid/<steam64>` URL of a clan, or `None` for other account
+    /// types.
+    pub fn group_url(&self) -> Option<String> {
+        if self.account_type() != AccountType::Clan {
+            return None;
+        }
+
+        Some(format!("https://steamcommunity.com/gid/{}", self.0))
+    }
+
+    /// Parses a `steamcommunity.com/gid/...` URL into a clan SteamID, accepting the same
+    /// variations as [`SteamID::from_community_url`]. IDs that aren't clans are rejected with
+    /// [`SteamIDParseErrorKind::InvalidAccountType`].
+    pub fn from_group_url(url: &str) -> Result<Self, SteamIDParseError> {
+        let (steamid, position) = community_id(url, "gid/")?;
+        if steamid.account_type() != AccountType::Clan {
+            return Err(
+                Cursor::new(url).error_at(SteamIDParseErrorKind::InvalidAccountType, position)
+            );
+        }
+
+        Ok(steamid)
+    }
+
+    /// The `partner=<account id>` query parameter that identifies an individual in a trade offer
+    /// URL, or `None` for other account types.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from(76561197960287930);
+    /// let url = format!(
+    ///     "https://steamcommunity.com/tradeoffer/new/?{}&token=abcdEFGH",
+    ///     s.trade_url_partner_param().unwrap()
+    /// );
+    /// assert_eq!(SteamID::from_trade_url(&url), Ok(s));
+    /// ```
+    pub fn trade_url_partner_param(&self) -> Option<String> {
+        if self.account_type() != AccountType::Individual {
+            return None;
+        }
+
+        Some(format!("partner={}", self.account_id()))
+    }
+
+    /// Parses the partner out of a `steamcommunity.com/tradeoffer/new/?partner=...` URL into an
+    /// individual account in the public universe. Other query parameters, like the token, are
+    /// ignored.
+    pub fn from_trade_url(url: &str) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::new(url);
+        let query = community_path(url)
+            .and_then(|path| path.strip_prefix("tradeoffer/new"))
+            .and_then(|rest| rest.strip_prefix('/').unwrap_or(rest).strip_prefix('?'))
+            .ok_or_else(|| cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0))?;
+        let mut pos = url.len() - query.len();
+        let query = &query[..query.find('#').unwrap_or(query.len())];
+
+        let mut partner = None;
+        for param in query.split('&') {
+            if param.starts_with("partner=") {
+                partner = Some(pos);
+                break;
+            }
+            pos += param.len() + 1;
+        }
+        let partner =
+            partner.ok_or_else(|| cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0))?;
+        cursor.pos = partner + "partner=".len();
+
+        let (account_id, _) = cursor.number(
+            u64::from(u32::MAX),
+            SteamIDParseErrorKind::AccountIdOverflow,
+        )?;
+        match cursor.peek() {
+            None | Some(b'&') | Some(b'#') => Ok(SteamID::new(
+                account_id as u32,
+                Instance::Desktop,
+                AccountType::Individual,
+                Universe::Public,
+            )),
+            Some(_) => Err(cursor.unexpected()),
+        }
+    }
+}
+
+// Strips the scheme and host from a steamcommunity.com URL, returning the path without its leading
+// slash
+pub(crate) fn community_path(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    rest.strip_prefix(COMMUNITY_HOST)
+}
+
+// Parses the id in a steamcommunity.com/<section><id> URL, which is either a steam64 or steam3 id.
+// Also returns where in the URL the id starts.
+fn community_id(url: &str, section: &str) -> Result<(SteamID, usize), SteamIDParseError> {
+    let mut cursor = Cursor::new(url);
+    let rest = community_path(url)
+        .and_then(|path| path.strip_prefix(section))
+        .ok_or_else(|| cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0))?;
+    cursor.pos = url.len() - rest.len();
+    let start = cursor.pos;
+
+    let steamid = if cursor.peek() == Some(b'[') {
+        let end = rest.find(']').map_or(rest.len(), |i| i + 1);
+        let steamid = SteamID::from_steam3_helper(&rest[..end]).map_err(|e| SteamIDParseError {
+            position: e.position.map(|p| p + cursor.pos),
+            ..e
+        })?;
+        cursor.pos += end;
+        steamid
+    } else {
+        // A run of digits too long for a steam64 isn't one
+        let (steam64, _) = cursor.number(u64::MAX, SteamIDParseErrorKind::WrongFormat)?;
+        SteamID(steam64)
+    };
+
+    match cursor.peek() {
+        None | Some(b'/') | Some(b'?') | Some(b'#') => Ok((steamid, start)),
+        Some(_) => Err(cursor.unexpected()),
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{AccountType, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe};
+
+impl SteamID {
+    /// A placeholder SteamID standing in for a PlayStation Network account, so crossplay code can
+    /// keep console players in the same maps and sets as Steam users.
+    ///
+    /// PSN account ids are 64-bit, but a SteamID only has room for a 32-bit account id. Ids past
+    /// `u32::MAX` can't be represented and fail with [`SteamIDParseErrorKind::AccountIdOverflow`]
+    /// rather than being truncated, so keep the original id around for players who have one.
+    ///
+    /// These are [`AccountType::ConsoleUser`] IDs in the public universe with the console
+    /// instance. The platform isn't recorded, so keep IDs from different platforms apart.
+    ///
+    /// ```
+    /// # use steamid_ng::{AccountType, SteamID, SteamIDParseErrorKind};
+    /// let s = SteamID::from_psn_id(1234).unwrap();
+    /// assert_eq!(s.account_type(), AccountType::ConsoleUser);
+    /// assert_eq!(s.account_id(), 1234);
+    /// assert_eq!(
+    ///     SteamID::from_psn_id(u64::MAX).unwrap_err().kind(),
+    ///     SteamIDParseErrorKind::AccountIdOverflow
+    /// );
+    /// ```
+    pub fn from_psn_id(psn_id: u64) -> Result<Self, SteamIDParseError> {
+        Self::console_user(psn_id)
+    }
+
+    /// Like [`SteamID::from_psn_id`], for an Xbox Live pairwise id. These are 64-bit too.
+    pub fn from_xbox_pairwise_id(xbox_id: u64) -> Result<Self, SteamIDParseError> {
+        Self::console_user(xbox_id)
+    }
+
+    /// Like [`SteamID::from_psn_id`], for a Nintendo Switch account id.
+    pub fn from_switch_id(switch_id: u64) -> Result<Self, SteamIDParseError> {
+        Self::console_user(switch_id)
+    }
+
+    pub fn is_console_user(&self) -> bool {
+        self.account_type() == AccountType::ConsoleUser
+    }
+
+    fn console_user(id: u64) -> Result<Self, SteamIDParseError> {
+        let account_id = u32::try_from(id)
+            .map_err(|_| SteamIDParseError::new(SteamIDParseErrorKind::AccountIdOverflow))?;
+        Ok(Self::new(
+            account_id,
+            Instance::Console,
+            AccountType::ConsoleUser,
+            Universe::Public,
+        ))
+    }
+}
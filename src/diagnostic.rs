@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use crate::{SteamIDParseError, SteamIDParseErrorKind};
+
+/// Labels point at [`position`](SteamIDParseError::position) in the parsed string. The error
+/// doesn't keep the input around, so attach it when reporting, e.g. with
+/// `miette::Report::new(error).with_source_code(input.to_owned())`.
+///
+/// Diagnostic codes are names like `steamid_ng::unexpected_character`. The numeric codes are
+/// still available from the inherent [`SteamIDParseError::code`], which takes precedence in method
+/// call syntax.
+impl Diagnostic for SteamIDParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.kind() {
+            SteamIDParseErrorKind::WrongFormat => "steamid_ng::wrong_format",
+            SteamIDParseErrorKind::UnexpectedCharacter => "steamid_ng::unexpected_character",
+            SteamIDParseErrorKind::UnexpectedEnd => "steamid_ng::unexpected_end",
+            SteamIDParseErrorKind::InvalidUniverse => "steamid_ng::invalid_universe",
+            SteamIDParseErrorKind::InvalidAccountType => "steamid_ng::invalid_account_type",
+            SteamIDParseErrorKind::InvalidInstance => "steamid_ng::invalid_instance",
+            SteamIDParseErrorKind::AccountIdOverflow => "steamid_ng::account_id_overflow",
+            SteamIDParseErrorKind::ChecksumMismatch => "steamid_ng::checksum_mismatch",
+            SteamIDParseErrorKind::UniverseZero => "steamid_ng::universe_zero",
+            SteamIDParseErrorKind::MissingAccountId => "steamid_ng::missing_account_id",
+        };
+        Some(Box::new(code))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let position = self.position()?;
+        let label = match self.kind() {
+            SteamIDParseErrorKind::WrongFormat => "not a steam64, steam2 or steam3 ID",
+            SteamIDParseErrorKind::UnexpectedCharacter => "unexpected character",
+            SteamIDParseErrorKind::UnexpectedEnd => "input ends here",
+            SteamIDParseErrorKind::InvalidUniverse => "expected a universe from 0 to 4",
+            SteamIDParseErrorKind::InvalidAccountType => "unknown account type",
+            SteamIDParseErrorKind::InvalidInstance => "instance doesn't fit in 20 bits",
+            SteamIDParseErrorKind::AccountIdOverflow => "account id doesn't fit in 32 bits",
+            SteamIDParseErrorKind::ChecksumMismatch => "checksum doesn't match, check for typos",
+            SteamIDParseErrorKind::UniverseZero => "legacy universe 0",
+            SteamIDParseErrorKind::MissingAccountId => "account id can't be 0",
+        };
+        let len = self.found().map_or(0, char::len_utf8);
+
+        Some(Box::new(std::iter::once(LabeledSpan::new(
+            Some(label.to_owned()),
+            position,
+            len,
+        ))))
+    }
+}
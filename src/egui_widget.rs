@@ -0,0 +1,189 @@
+use std::str::FromStr;
+
+use egui::{AsIdSalt, Color32, ComboBox, DragValue, Grid, Id, Response, TextEdit, Ui, Widget};
+
+use crate::{AccountType, Instance, SteamID, Universe};
+
+const INSTANCES: &[Instance] = &[
+    Instance::All,
+    Instance::Desktop,
+    Instance::Console,
+    Instance::Web,
+    Instance::FlagClan,
+    Instance::FlagLobby,
+    Instance::FlagMMSLobby,
+];
+
+const ACCOUNT_TYPES: &[AccountType] = &[
+    AccountType::Invalid,
+    AccountType::Individual,
+    AccountType::Multiseat,
+    AccountType::GameServer,
+    AccountType::AnonGameServer,
+    AccountType::Pending,
+    AccountType::ContentServer,
+    AccountType::Clan,
+    AccountType::Chat,
+    AccountType::ConsoleUser,
+    AccountType::AnonUser,
+];
+
+const UNIVERSES: &[Universe] = &[
+    Universe::Invalid,
+    Universe::Public,
+    Universe::Beta,
+    Universe::Internal,
+    Universe::Dev,
+];
+
+/// An inspector for a SteamID: shows its steam64, steam2 and steam3 renderings, and lets the user
+/// paste a new ID in any format or edit the individual components.
+///
+/// ```no_run
+/// # fn show(ui: &mut egui::Ui, steamid: &mut steamid_ng::SteamID) {
+/// if ui.add(steamid_ng::SteamIDEditor::new(steamid)).changed() {
+///     // steamid was edited
+/// }
+/// # }
+/// ```
+pub struct SteamIDEditor<'a> {
+    steamid: &'a mut SteamID,
+    id_salt: Option<Id>,
+}
+
+impl<'a> SteamIDEditor<'a> {
+    pub fn new(steamid: &'a mut SteamID) -> Self {
+        SteamIDEditor {
+            steamid,
+            id_salt: None,
+        }
+    }
+
+    /// Needed when showing several editors in the same `Ui`, to keep their state apart.
+    pub fn id_salt(mut self, id_salt: impl AsIdSalt) -> Self {
+        self.id_salt = Some(Id::new("steamid_editor").with(id_salt));
+        self
+    }
+}
+
+impl Widget for SteamIDEditor<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let steamid = self.steamid;
+        let id = ui.make_persistent_id(self.id_salt.unwrap_or_else(|| Id::new("steamid_editor")));
+        let mut changed = false;
+        let mut changed_by_text = false;
+
+        // The text field keeps its own buffer so half-typed input survives between frames. It's
+        // reset whenever the ID is changed from elsewhere.
+        let (mut text, synced_with) = ui.data_mut(|d| {
+            d.get_temp::<(String, SteamID)>(id)
+                .unwrap_or_else(|| (steamid.steam3(), *steamid))
+        });
+        if synced_with != *steamid {
+            text = steamid.steam3();
+        }
+
+        let mut response = Grid::new(id)
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Parse");
+                let (parsed, edited) = {
+                    let edit = ui.add(TextEdit::singleline(&mut text).hint_text("Any SteamID"));
+                    (SteamID::from_str(text.trim()), edit.changed())
+                };
+                ui.end_row();
+
+                match parsed {
+                    Ok(parsed) if edited && parsed != *steamid => {
+                        *steamid = parsed;
+                        changed_by_text = true;
+                    }
+                    Err(_) => {
+                        ui.label("");
+                        ui.colored_label(Color32::RED, "Malformed SteamID");
+                        ui.end_row();
+                    }
+                    _ => (),
+                }
+
+                ui.label("steam64");
+                ui.monospace(u64::from(*steamid).to_string());
+                ui.end_row();
+
+                ui.label("steam2");
+                ui.monospace(steamid.steam2());
+                ui.end_row();
+
+                ui.label("steam3");
+                ui.monospace(steamid.steam3());
+                ui.end_row();
+
+                ui.label("Account ID");
+                let mut account_id = steamid.account_id();
+                if ui.add(DragValue::new(&mut account_id)).changed() {
+                    steamid.set_account_id(account_id);
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Instance");
+                let mut instance = steamid.instance();
+                if combo(ui, id.with("instance"), &mut instance, INSTANCES) {
+                    steamid.set_instance(instance);
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Account type");
+                let mut account_type = steamid.account_type();
+                if combo(
+                    ui,
+                    id.with("account_type"),
+                    &mut account_type,
+                    ACCOUNT_TYPES,
+                ) {
+                    steamid.set_account_type(account_type);
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Universe");
+                let mut universe = steamid.universe();
+                if combo(ui, id.with("universe"), &mut universe, UNIVERSES) {
+                    steamid.set_universe(universe);
+                    changed = true;
+                }
+                ui.end_row();
+            })
+            .response;
+
+        // Editing a component rewrites the text; a parsed edit keeps what the user typed
+        if changed {
+            text = steamid.steam3();
+        }
+        let steamid = *steamid;
+        ui.data_mut(|d| d.insert_temp(id, (text, steamid)));
+
+        if changed || changed_by_text {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+fn combo<T: Copy + PartialEq + std::fmt::Display>(
+    ui: &mut Ui,
+    id: Id,
+    current: &mut T,
+    options: &[T],
+) -> bool {
+    let before = *current;
+    ComboBox::from_id_salt(id)
+        .selected_text(current.to_string())
+        .show_ui(ui, |ui| {
+            for &option in options {
+                ui.selectable_value(current, option, option.to_string());
+            }
+        });
+    *current != before
+}
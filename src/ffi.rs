@@ -0,0 +1,171 @@
+//! A small string-in/string-out surface for other languages.
+//!
+//! [`convert`] only uses plain Rust types, so binding generators like flutter_rust_bridge can
+//! wrap it directly. The `extern "C"` functions below do the same over the C ABI (for `dart:ffi`
+//! and friends), reporting failures through the `STEAMID_*` status codes. They're exported from
+//! whichever `cdylib` or `staticlib` ends up linking this crate.
+
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+    str::FromStr,
+};
+
+use crate::SteamID;
+
+pub const STEAMID_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const STEAMID_ERR_NULL: c_int = 1;
+/// The input wasn't valid UTF-8.
+pub const STEAMID_ERR_UTF8: c_int = 2;
+/// The input wasn't a SteamID in any supported format.
+pub const STEAMID_ERR_PARSE: c_int = 3;
+/// The output buffer can't hold the result and its terminating NUL.
+pub const STEAMID_ERR_BUFFER_TOO_SMALL: c_int = 4;
+/// The requested output format doesn't exist.
+pub const STEAMID_ERR_FORMAT: c_int = 5;
+
+/// Output formats for [`convert`], with stable numeric values for the C ABI.
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum OutputFormat {
+    Steam64 = 0,
+    Steam2 = 1,
+    Steam3 = 2,
+}
+
+impl OutputFormat {
+    pub fn from_u32(format: u32) -> Option<Self> {
+        match format {
+            0 => Some(OutputFormat::Steam64),
+            1 => Some(OutputFormat::Steam2),
+            2 => Some(OutputFormat::Steam3),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `input` in any format `SteamID::from_str` understands and renders it as `format`. The
+/// error is one of the `STEAMID_ERR_*` codes.
+pub fn convert(input: &str, format: OutputFormat) -> Result<String, c_int> {
+    let steamid = SteamID::from_str(input).map_err(|_| STEAMID_ERR_PARSE)?;
+    Ok(match format {
+        OutputFormat::Steam64 => u64::from(steamid).to_string(),
+        OutputFormat::Steam2 => steamid.steam2(),
+        OutputFormat::Steam3 => steamid.steam3(),
+    })
+}
+
+/// Parses the NUL-terminated string `input` into a steam64, written to `out`.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string, and `out` must be null or valid for
+/// writing a `u64`.
+#[no_mangle]
+pub unsafe extern "C" fn steamid_parse(input: *const c_char, out: *mut u64) -> c_int {
+    steamid_parse_with_code(input, out, std::ptr::null_mut())
+}
+
+/// Like [`steamid_parse`], but on `STEAMID_ERR_PARSE` also writes the parse error's
+/// [`code`](crate::SteamIDParseError::code) to `out_code`, unless it is null.
+///
+/// # Safety
+///
+/// As for [`steamid_parse`], and `out_code` must be null or valid for writing a `u32`.
+#[no_mangle]
+pub unsafe extern "C" fn steamid_parse_with_code(
+    input: *const c_char,
+    out: *mut u64,
+    out_code: *mut u32,
+) -> c_int {
+    if input.is_null() || out.is_null() {
+        return STEAMID_ERR_NULL;
+    }
+
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return STEAMID_ERR_UTF8,
+    };
+    match SteamID::from_str(input) {
+        Ok(steamid) => {
+            *out = steamid.into();
+            STEAMID_OK
+        }
+        Err(e) => {
+            if !out_code.is_null() {
+                *out_code = e.code();
+            }
+            STEAMID_ERR_PARSE
+        }
+    }
+}
+
+/// Converts the NUL-terminated string `input` to `format` (an [`OutputFormat`] value), writing a
+/// NUL-terminated result into the `out_len` bytes at `out`. Nothing is written on failure.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string, and `out` must be null or valid for
+/// writing `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn steamid_convert(
+    input: *const c_char,
+    format: u32,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    if input.is_null() || out.is_null() {
+        return STEAMID_ERR_NULL;
+    }
+
+    let format = match OutputFormat::from_u32(format) {
+        Some(format) => format,
+        None => return STEAMID_ERR_FORMAT,
+    };
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return STEAMID_ERR_UTF8,
+    };
+    match convert(input, format) {
+        Ok(rendered) => write_c_string(&rendered, out, out_len),
+        Err(code) => code,
+    }
+}
+
+/// Renders `steam64` as `format` (an [`OutputFormat`] value), writing a NUL-terminated result
+/// into the `out_len` bytes at `out`. Nothing is written on failure.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writing `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn steamid_render(
+    steam64: u64,
+    format: u32,
+    out: *mut c_char,
+    out_len: usize,
+) -> c_int {
+    if out.is_null() {
+        return STEAMID_ERR_NULL;
+    }
+
+    let steamid = SteamID::from(steam64);
+    let rendered = match OutputFormat::from_u32(format) {
+        Some(OutputFormat::Steam64) => steam64.to_string(),
+        Some(OutputFormat::Steam2) => steamid.steam2(),
+        Some(OutputFormat::Steam3) => steamid.steam3(),
+        None => return STEAMID_ERR_FORMAT,
+    };
+    write_c_string(&rendered, out, out_len)
+}
+
+unsafe fn write_c_string(s: &str, out: *mut c_char, out_len: usize) -> c_int {
+    if s.len() >= out_len {
+        return STEAMID_ERR_BUFFER_TOO_SMALL;
+    }
+
+    std::ptr::copy_nonoverlapping(s.as_ptr() as *const c_char, out, s.len());
+    *out.add(s.len()) = 0;
+    STEAMID_OK
+}
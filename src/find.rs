@@ -0,0 +1,117 @@
+//! Finding SteamIDs in free text, see [`SteamID::find_all`].
+
+use std::ops::Range;
+
+use crate::{SteamID, Universe};
+
+const URL_PREFIXES: &[&str] = &["https://", "http://", "www.", "steamcommunity.com/"];
+
+// The longest steam3 ID, `[A:4:4294967295:1048575]`, with room to spare
+const MAX_STEAM3_LEN: usize = 32;
+
+// The shortest steam64 in the public universe, as individuals' are
+const MIN_STEAM64_DIGITS: usize = 17;
+
+impl SteamID {
+    /// Finds every steam2 ID, steam3 ID, steam64 and steamcommunity.com profile or group URL in
+    /// `text`, along with the byte range it occupies.
+    ///
+    /// To keep false positives down, an ID has to be a word of its own, not part of a longer run
+    /// of letters and digits. Bare numbers only count as steam64s if they have at least 17 digits
+    /// and are valid according to [`SteamID::from_steam64`], with a universe other than
+    /// [`Universe::Invalid`].
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let appeal = "I'm STEAM_1:0:11101, not [U:1:22203]!";
+    /// let found: Vec<_> = SteamID::find_all(appeal).collect();
+    /// assert_eq!(found[0], (4..19, SteamID::from(76561197960287930)));
+    /// assert_eq!(found[1], (25..36, SteamID::from(76561197960287931)));
+    /// ```
+    pub fn find_all(text: &str) -> impl Iterator<Item = (Range<usize>, SteamID)> + '_ {
+        FindAll { text, pos: 0 }
+    }
+}
+
+struct FindAll<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Iterator for FindAll<'_> {
+    type Item = (Range<usize>, SteamID);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+        while self.pos < bytes.len() {
+            let start = self.pos;
+            self.pos += 1;
+
+            // Every format starts with an ASCII character, which is always a char boundary
+            let at_word_start = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+            if !bytes[start].is_ascii() || !at_word_start {
+                continue;
+            }
+
+            if let Some((len, steamid)) = match_at(&self.text[start..]) {
+                self.pos = start + len;
+                return Some((start..start + len, steamid));
+            }
+        }
+
+        None
+    }
+}
+
+// Matches an ID at the very start of `rest`, returning its length
+fn match_at(rest: &str) -> Option<(usize, SteamID)> {
+    let bytes = rest.as_bytes();
+    let run = |from: usize, allowed: fn(&u8) -> bool| {
+        from + bytes[from..].iter().take_while(|b| allowed(b)).count()
+    };
+    let ends_word = |end: usize| bytes.get(end).map_or(true, |b| !b.is_ascii_alphanumeric());
+
+    if rest.starts_with("STEAM_") {
+        let end = run("STEAM_".len(), |&b| b.is_ascii_digit() || b == b':');
+        // Leave a colon that ends a sentence ("I'm STEAM_1:0:11101:") out of the ID
+        let steam2 = rest[..end].trim_end_matches(':');
+        return SteamID::from_steam2_helper(steam2)
+            .ok()
+            .filter(|_| ends_word(steam2.len()))
+            .map(|steamid| (steam2.len(), steamid));
+    }
+
+    if rest.starts_with('[') {
+        let end = bytes.iter().take(MAX_STEAM3_LEN).position(|&b| b == b']')? + 1;
+        return SteamID::from_steam3_helper(&rest[..end])
+            .ok()
+            .map(|steamid| (end, steamid));
+    }
+
+    if bytes[0].is_ascii_digit() {
+        let end = run(0, u8::is_ascii_digit);
+        if end < MIN_STEAM64_DIGITS || !ends_word(end) {
+            return None;
+        }
+        return rest[..end]
+            .parse()
+            .ok()
+            .and_then(|steam64| SteamID::from_steam64(steam64).ok())
+            .filter(|steamid| steamid.universe() != Universe::Invalid)
+            .map(|steamid| (end, steamid));
+    }
+
+    if URL_PREFIXES.iter().any(|prefix| rest.starts_with(prefix)) {
+        let end = run(0, |&b| {
+            !b.is_ascii_whitespace() && !matches!(b, b'"' | b'\'' | b'<' | b'>')
+        });
+        // Punctuation around a link in prose isn't part of it
+        let url = rest[..end].trim_end_matches(&['.', ',', ';', ':', '!', '?', ')'][..]);
+        return SteamID::from_community_url(url)
+            .or_else(|_| SteamID::from_group_url(url))
+            .ok()
+            .map(|steamid| (url.len(), steamid));
+    }
+
+    None
+}
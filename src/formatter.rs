@@ -0,0 +1,192 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::SteamID;
+
+/// Renders SteamIDs after a template, for display conventions the built-in formats don't cover.
+/// Fields are written in braces, and `{{` and `}}` stand for literal braces:
+///
+/// | Field                  | Example             |
+/// |------------------------|---------------------|
+/// | `{steam64}`            | `76561197960287930` |
+/// | `{steam2}`             | `STEAM_1:0:11101`   |
+/// | `{steam3}`             | `[U:1:22202]`       |
+/// | `{steam3_bare}`        | `U:1:22202`         |
+/// | `{account_id}`         | `22202`             |
+/// | `{instance}` (raw)     | `1`                 |
+/// | `{account_type}` (raw) | `1`                 |
+/// | `{account_type_name}`  | `Individual`        |
+/// | `{type_char}`          | `U`                 |
+/// | `{universe}` (raw)     | `1`                 |
+/// | `{universe_name}`      | `Public`            |
+///
+/// ```
+/// # use steamid_ng::{SteamID, SteamIDFormatter};
+/// let formatter = SteamIDFormatter::new("{type_char}:{universe}:{account_id}").unwrap();
+/// assert_eq!(formatter.format(SteamID::from(76561197960287930)), "U:1:22202");
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SteamIDFormatter {
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum Field {
+    Steam64,
+    Steam2,
+    Steam3,
+    Steam3Bare,
+    AccountId,
+    Instance,
+    AccountType,
+    AccountTypeName,
+    TypeChar,
+    Universe,
+    UniverseName,
+}
+
+const FIELDS: &[(&str, Field)] = &[
+    ("steam64", Field::Steam64),
+    ("steam2", Field::Steam2),
+    ("steam3", Field::Steam3),
+    ("steam3_bare", Field::Steam3Bare),
+    ("account_id", Field::AccountId),
+    ("instance", Field::Instance),
+    ("account_type", Field::AccountType),
+    ("account_type_name", Field::AccountTypeName),
+    ("type_char", Field::TypeChar),
+    ("universe", Field::Universe),
+    ("universe_name", Field::UniverseName),
+];
+
+const PRESETS: &[(&str, &str)] = &[
+    ("steam64", "{steam64}"),
+    ("steam2", "{steam2}"),
+    ("steam3", "{steam3}"),
+    ("steam3_bare", "{steam3_bare}"),
+    (
+        "community_url",
+        "https://steamcommunity.com/profiles/{steam64}",
+    ),
+];
+
+impl SteamIDFormatter {
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+
+        while let Some(i) = rest.find(['{', '}']) {
+            literal.push_str(&rest[..i]);
+            let position = template.len() - rest.len() + i;
+            let after = &rest[i + 1..];
+
+            if rest[i..].starts_with("{{") || rest[i..].starts_with("}}") {
+                literal.push_str(&rest[i..=i]);
+                rest = &after[1..];
+                continue;
+            }
+            if rest.as_bytes()[i] == b'}' {
+                return Err(TemplateError { position });
+            }
+
+            let end = after.find('}').ok_or(TemplateError { position })?;
+            let field = FIELDS
+                .iter()
+                .find(|(name, _)| *name == &after[..end])
+                .ok_or(TemplateError { position })?
+                .1;
+            if !literal.is_empty() {
+                parts.push(Part::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(Part::Field(field));
+            rest = &after[end + 1..];
+        }
+
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(SteamIDFormatter { parts })
+    }
+
+    /// One of the built-in templates: `steam64`, `steam2`, `steam3`, `steam3_bare` (as
+    /// [`SteamID::steam3_bare`] renders it) or `community_url`.
+    pub fn preset(name: &str) -> Option<Self> {
+        let &(_, template) = PRESETS.iter().find(|(preset, _)| *preset == name)?;
+        Some(Self::new(template).expect("Presets are valid"))
+    }
+
+    pub fn format(&self, steamid: SteamID) -> String {
+        let mut formatted = String::new();
+        self.write(steamid, &mut formatted)
+            .expect("Writing to a String can't fail");
+        formatted
+    }
+
+    /// Writes `steamid` to `w` after the template, without allocating.
+    pub fn write<W: fmt::Write + ?Sized>(&self, steamid: SteamID, w: &mut W) -> fmt::Result {
+        for part in &self.parts {
+            let field = match part {
+                Part::Literal(literal) => {
+                    w.write_str(literal)?;
+                    continue;
+                }
+                Part::Field(field) => *field,
+            };
+
+            match field {
+                Field::Steam64 => write!(w, "{}", u64::from(steamid))?,
+                Field::Steam2 => steamid.write_steam2(w)?,
+                Field::Steam3 => steamid.write_steam3(w)?,
+                Field::Steam3Bare => steamid.write_steam3_bare(w)?,
+                Field::AccountId => write!(w, "{}", steamid.account_id())?,
+                Field::Instance => write!(w, "{}", steamid.instance_value())?,
+                Field::AccountType => write!(w, "{}", steamid.account_type_value())?,
+                Field::AccountTypeName => write!(w, "{}", steamid.account_type())?,
+                Field::TypeChar => w.write_char(steamid.type_char())?,
+                Field::Universe => write!(w, "{}", steamid.universe_value())?,
+                Field::UniverseName => write!(w, "{}", steamid.universe())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SteamIDFormatter {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::new(template)
+    }
+}
+
+/// A template with an unknown field or an unmatched brace.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TemplateError {
+    position: usize,
+}
+
+impl TemplateError {
+    /// The byte offset of the offending brace.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Error for TemplateError {}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Malformed SteamID template at byte {}", self.position)
+    }
+}
@@ -0,0 +1,104 @@
+//! CS:GO / CS2 friend codes, such as `SUCVS-FADA`.
+//!
+//! A friend code is the account id, interleaved nibble by nibble with bits of an MD5 hash of it,
+//! written in a 32-letter alphabet. The hash bits act as a checksum, so typos are caught when
+//! decoding.
+
+use crate::{
+    AccountType, Cursor, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe,
+};
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+// Each character holds 5 bits. Rendered codes leave out the first 4 characters, which are
+// always 'A'.
+const SKIPPED_CHARS: u32 = 4;
+const CODE_CHARS: usize = 9;
+
+fn interleave(account_id: u32) -> u64 {
+    // "CSGO" followed by the account id, byte-reversed
+    let input = (0x4353474F_u64 << 32 | u64::from(account_id)).to_le_bytes();
+    let digest = md5::compute(input);
+    let hash = u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+    let mut result = 0u64;
+    for i in 0..8 {
+        let id_nibble = u64::from(account_id >> (i * 4)) & 0xF;
+        let hash_bit = u64::from(hash >> i) & 0x1;
+        let a = (result << 4) | id_nibble;
+
+        result = ((result >> 28) << 32) | a;
+        result = ((result >> 31) << 32) | (a << 1) | hash_bit;
+    }
+    result.swap_bytes()
+}
+
+impl SteamID {
+    /// The CS:GO / CS2 friend code of an individual account, or `None` for other account types.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// assert_eq!(
+    ///     SteamID::from(76561197960287930).friend_code().as_deref(),
+    ///     Some("SUCVS-FADA")
+    /// );
+    /// ```
+    pub fn friend_code(&self) -> Option<String> {
+        if self.account_type() != AccountType::Individual {
+            return None;
+        }
+
+        let mut bits = interleave(self.account_id()) >> (5 * SKIPPED_CHARS);
+        let mut code = String::with_capacity(CODE_CHARS + 1);
+        for i in 0..CODE_CHARS {
+            if i == 5 {
+                code.push('-');
+            }
+            code.push(char::from(ALPHABET[(bits & 0x1F) as usize]));
+            bits >>= 5;
+        }
+        Some(code)
+    }
+
+    /// Decodes a friend code into an individual account in the public universe. Codes whose
+    /// checksum doesn't match fail with [`SteamIDParseErrorKind::ChecksumMismatch`].
+    pub fn from_friend_code(code: &str) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::new(code);
+        let mut bits = 0u64;
+        for i in 0..CODE_CHARS {
+            if i == 5 {
+                cursor.expect(b'-')?;
+            }
+            let index = match cursor
+                .peek()
+                .and_then(|b| ALPHABET.iter().position(|&c| c == b))
+            {
+                Some(index) => index as u64,
+                None => return Err(cursor.unexpected()),
+            };
+            cursor.next();
+            bits |= index << (5 * (SKIPPED_CHARS + i as u32));
+        }
+        cursor.end()?;
+
+        let mut bits = bits.swap_bytes();
+        let mut account_id = 0u32;
+        for _ in 0..8 {
+            bits >>= 1;
+            account_id = (account_id << 4) | (bits & 0xF) as u32;
+            bits >>= 4;
+        }
+
+        let steamid = SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        );
+        if steamid.friend_code().as_deref() != Some(code) {
+            return Err(cursor.error_at(SteamIDParseErrorKind::ChecksumMismatch, 0));
+        }
+
+        Ok(steamid)
+    }
+}
@@ -0,0 +1,20 @@
+use std::any::Any;
+
+use fyrox_core::{
+    reflect::{blank_reflect, prelude::*},
+    visitor::prelude::*,
+};
+
+use crate::SteamID;
+
+// Reflected as an opaque value, the same way Fyrox treats `Uuid`
+impl Reflect for SteamID {
+    blank_reflect!();
+}
+
+// Stored as the steam64
+impl Visit for SteamID {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        self.0.visit(name, visitor)
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use enum_primitive::FromPrimitive;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::ids::id_newtype;
+
+id_newtype!(
+    /// Valve's 64-bit `CGameID`, as rich presence, server browsers and `shortcuts.vdf` use: an
+    /// app id packed together with what kind of game it is and, for mods and non-Steam
+    /// shortcuts, an id telling it apart from others sharing the app id.
+    ///
+    /// | Bits  | Field                                               |
+    /// |-------|-----------------------------------------------------|
+    /// | 0-23  | App id                                              |
+    /// | 24-31 | [`GameIDType`]                                      |
+    /// | 32-63 | Mod id, with the top bit set for mods and shortcuts |
+    ///
+    /// ```
+    /// # use steamid_ng::{GameID, GameIDType};
+    /// let tf2 = GameID::from_app_id(440);
+    /// assert_eq!(u64::from(tf2), 440);
+    /// assert_eq!(tf2.game_type(), Some(GameIDType::App));
+    ///
+    /// let shortcut = GameID::from_shortcut("\"C:\\Games\\game.exe\"", "My Game");
+    /// assert!(shortcut.is_shortcut());
+    /// ```
+    GameID(u64),
+    "a game id"
+);
+
+enum_from_primitive!(
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    pub enum GameIDType {
+        App = 0,
+        GameMod = 1,
+        Shortcut = 2,
+        P2P = 3,
+    }
+);
+
+const APP_ID_MASK: u64 = 0xFF_FFFF;
+// Set on the mod id of mods and shortcuts, so it's never confused with an app id
+const MOD_ID_FLAG: u32 = 0x8000_0000;
+
+impl GameID {
+    pub fn new(app_id: u32, game_type: GameIDType, mod_id: u32) -> Self {
+        GameID(u64::from(app_id) & APP_ID_MASK | (game_type as u64) << 24 | u64::from(mod_id) << 32)
+    }
+
+    /// A plain Steam app. App ids only have 24 bits of room, so the rest of `app_id` is dropped.
+    pub fn from_app_id(app_id: u32) -> Self {
+        Self::new(app_id, GameIDType::App, 0)
+    }
+
+    /// A mod of `app_id`, such as a Source mod in `sourcemods`, identified by the name of its
+    /// directory.
+    pub fn from_mod(app_id: u32, mod_dir: &str) -> Self {
+        Self::new(
+            app_id,
+            GameIDType::GameMod,
+            crc32(mod_dir.as_bytes()) | MOD_ID_FLAG,
+        )
+    }
+
+    /// A non-Steam game added to the library, identified by its executable path (quoted, as the
+    /// Steam client stores it) and name. This is the id Steam uses for the shortcut's grid art and
+    /// `steam://rungameid/` URLs.
+    pub fn from_shortcut(exe: &str, app_name: &str) -> Self {
+        let mut input = Vec::with_capacity(exe.len() + app_name.len());
+        input.extend_from_slice(exe.as_bytes());
+        input.extend_from_slice(app_name.as_bytes());
+        Self::new(0, GameIDType::Shortcut, crc32(&input) | MOD_ID_FLAG)
+    }
+
+    pub fn app_id(&self) -> u32 {
+        (self.0 & APP_ID_MASK) as u32
+    }
+
+    /// `None` for types Valve has yet to define.
+    pub fn game_type(&self) -> Option<GameIDType> {
+        GameIDType::from_u64((self.0 >> 24) & 0xFF)
+    }
+
+    /// The mod id, including its top bit if set.
+    pub fn mod_id(&self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    pub fn is_steam_app(&self) -> bool {
+        self.game_type() == Some(GameIDType::App)
+    }
+
+    pub fn is_mod(&self) -> bool {
+        self.game_type() == Some(GameIDType::GameMod)
+    }
+
+    pub fn is_shortcut(&self) -> bool {
+        self.game_type() == Some(GameIDType::Shortcut)
+    }
+
+    pub fn is_p2p_file(&self) -> bool {
+        self.game_type() == Some(GameIDType::P2P)
+    }
+}
+
+// CRC-32 (IEEE), as CGameID hashes mod and shortcut names with
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
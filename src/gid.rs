@@ -0,0 +1,90 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::ids::id_newtype;
+
+id_newtype!(
+    /// Valve's 64-bit global id, which the CM protocol also uses as job ids to route responses
+    /// back to requests. It's made unique by who handed it out and when:
+    ///
+    /// | Bits  | Field                              |
+    /// |-------|------------------------------------|
+    /// | 0-19  | Sequence count, within the process |
+    /// | 20-49 | Start time of the process          |
+    /// | 50-53 | Process id, within the box         |
+    /// | 54-63 | Box id                             |
+    ///
+    /// ```
+    /// # use steamid_ng::GlobalID;
+    /// let gid = GlobalID::new(12, 3, 400_000_000, 5);
+    /// assert_eq!(gid.box_id(), 12);
+    /// assert_eq!(gid.process_id(), 3);
+    /// assert_eq!(gid.sequence_count(), 5);
+    /// assert!(GlobalID::NIL.is_nil());
+    /// ```
+    GlobalID(u64),
+    "a global id"
+);
+
+/// The same layout, under the name the CM protocol uses.
+pub type JobID = GlobalID;
+
+// Start times count seconds from 2005-01-01T00:00:00Z
+const START_TIME_EPOCH: u64 = 1_104_537_600;
+
+const SEQUENCE_MASK: u64 = 0xF_FFFF;
+const START_TIME_MASK: u64 = 0x3FFF_FFFF;
+const PROCESS_ID_MASK: u64 = 0xF;
+const BOX_ID_MASK: u64 = 0x3FF;
+
+impl GlobalID {
+    /// `k_GIDNil`, which stands for no id at all, such as a message that isn't part of a job.
+    pub const NIL: GlobalID = GlobalID(u64::MAX);
+
+    /// Builds an id from its fields, dropping any bits they don't have room for. `start_time` is
+    /// in seconds since 2005.
+    pub fn new(box_id: u16, process_id: u8, start_time: u32, sequence_count: u32) -> Self {
+        GlobalID(
+            (u64::from(box_id) & BOX_ID_MASK) << 54
+                | (u64::from(process_id) & PROCESS_ID_MASK) << 50
+                | (u64::from(start_time) & START_TIME_MASK) << 20
+                | u64::from(sequence_count) & SEQUENCE_MASK,
+        )
+    }
+
+    pub fn is_nil(&self) -> bool {
+        *self == Self::NIL
+    }
+
+    pub fn sequence_count(&self) -> u32 {
+        (self.0 & SEQUENCE_MASK) as u32
+    }
+
+    /// When the issuing process started, in seconds since 2005.
+    pub fn start_time_seconds(&self) -> u32 {
+        ((self.0 >> 20) & START_TIME_MASK) as u32
+    }
+
+    /// [`GlobalID::start_time_seconds`] as a point in time.
+    pub fn start_time(&self) -> SystemTime {
+        let seconds = START_TIME_EPOCH + u64::from(self.start_time_seconds());
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    pub fn process_id(&self) -> u8 {
+        ((self.0 >> 50) & PROCESS_ID_MASK) as u8
+    }
+
+    pub fn box_id(&self) -> u16 {
+        ((self.0 >> 54) & BOX_ID_MASK) as u16
+    }
+}
@@ -0,0 +1,35 @@
+//! Rendering into fixed-capacity `heapless::String`s, for targets without a heap.
+
+use heapless::String;
+
+use crate::SteamID;
+
+impl SteamID {
+    /// [`SteamID::steam2`] without allocating: the capacity always fits.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let steam2 = SteamID::from(76561197960287930).steam2_array();
+    /// assert_eq!(steam2, "STEAM_1:0:11101");
+    /// ```
+    pub fn steam2_array(&self) -> String<{ SteamID::STEAM2_MAX_LEN }> {
+        let mut steam2 = String::new();
+        self.write_steam2(&mut steam2)
+            .expect("STEAM2_MAX_LEN fits every steam2 ID");
+        steam2
+    }
+
+    /// [`SteamID::steam3`] without allocating: the capacity always fits.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let steam3 = SteamID::from(76561197960287930).steam3_array();
+    /// assert_eq!(steam3, "[U:1:22202]");
+    /// ```
+    pub fn steam3_array(&self) -> String<{ SteamID::STEAM3_MAX_LEN }> {
+        let mut steam3 = String::new();
+        self.write_steam3(&mut steam3)
+            .expect("STEAM3_MAX_LEN fits every steam3 ID");
+        steam3
+    }
+}
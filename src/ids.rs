@@ -0,0 +1,266 @@
+//! Plain numeric Steam identifiers that don't carry any packed fields.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{community::community_path, SteamID};
+
+macro_rules! id_newtype {
+    ($(#[$meta:meta])* $name:ident($inner:ident), $expecting:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Debug)]
+        pub struct $name($inner);
+
+        impl From<$inner> for $name {
+            fn from(id: $inner) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map($name)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        // Accepts both numbers and numeric strings, since the Web API is inconsistent about which
+        // one it hands out.
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct IdVisitor;
+                impl<'de> Visitor<'de> for IdVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str($expecting)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<$name, E>
+                    where
+                        E: de::Error,
+                    {
+                        value.parse().map_err(|_| {
+                            E::custom(format!(concat!("Invalid ", stringify!($name), ": {}"), value))
+                        })
+                    }
+
+                    fn visit_u64<E>(self, value: u64) -> Result<$name, E>
+                    where
+                        E: de::Error,
+                    {
+                        $inner::try_from(value).map($name).map_err(|_| {
+                            E::invalid_value(de::Unexpected::Unsigned(value), &self)
+                        })
+                    }
+                }
+
+                deserializer.deserialize_any(IdVisitor)
+            }
+        }
+    };
+}
+
+pub(crate) use id_newtype;
+
+id_newtype!(
+    /// The 32-bit account id of a [`SteamID`], without the instance, type and universe around it.
+    AccountID(u32),
+    "an account id"
+);
+
+/// The individual account in the public universe, like [`SteamID::from_steam32`].
+impl From<AccountID> for SteamID {
+    fn from(id: AccountID) -> Self {
+        SteamID::from_steam32(id.0)
+    }
+}
+
+impl SteamID {
+    /// [`SteamID::account_id`] as an [`AccountID`].
+    pub fn account_id_typed(&self) -> AccountID {
+        AccountID(self.account_id())
+    }
+}
+
+id_newtype!(
+    /// An app: a game, tool, DLC or anything else with a store page.
+    ///
+    /// ```
+    /// # use steamid_ng::AppID;
+    /// let url = "https://store.steampowered.com/app/440/Team_Fortress_2/";
+    /// assert_eq!(AppID::from_url(url), Some(AppID::from(440)));
+    /// ```
+    AppID(u32),
+    "an app id"
+);
+
+impl AppID {
+    /// Parses a store (`store.steampowered.com/app/<id>`) or SteamDB (`steamdb.info/app/<id>`)
+    /// URL. The scheme and `www.` are optional, and anything after the id is ignored.
+    pub fn from_url(url: &str) -> Option<Self> {
+        url_id(url, &[STORE_HOST, STEAMDB_HOST], "app/").map(AppID)
+    }
+
+    pub fn store_url(&self) -> String {
+        format!("https://store.steampowered.com/app/{}/", self.0)
+    }
+}
+
+id_newtype!(
+    /// A depot, one of the sets of files an app's builds are split into for download.
+    DepotID(u32),
+    "a depot id"
+);
+
+impl DepotID {
+    /// Parses a SteamDB `steamdb.info/depot/<id>` URL, the way [`AppID::from_url`] does.
+    pub fn from_url(url: &str) -> Option<Self> {
+        url_id(url, &[STEAMDB_HOST], "depot/").map(DepotID)
+    }
+}
+
+id_newtype!(
+    /// A package, also called a sub: the bundle of apps and depots a license grants.
+    ///
+    /// ```
+    /// # use steamid_ng::PackageID;
+    /// let url = "https://store.steampowered.com/sub/469/";
+    /// assert_eq!(PackageID::from_url(url), Some(PackageID::from(469)));
+    /// ```
+    PackageID(u32),
+    "a package id"
+);
+
+impl PackageID {
+    /// Parses a store (`store.steampowered.com/sub/<id>`) or SteamDB (`steamdb.info/sub/<id>`)
+    /// URL, the way [`AppID::from_url`] does.
+    pub fn from_url(url: &str) -> Option<Self> {
+        url_id(url, &[STORE_HOST, STEAMDB_HOST], "sub/").map(PackageID)
+    }
+
+    pub fn store_url(&self) -> String {
+        format!("https://store.steampowered.com/sub/{}/", self.0)
+    }
+}
+
+const STORE_HOST: &str = "store.steampowered.com/";
+const STEAMDB_HOST: &str = "steamdb.info/";
+
+// Parses the id in `<host><section><id>`, which can be followed by a path, query or fragment
+fn url_id(url: &str, hosts: &[&str], section: &str) -> Option<u32> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let rest = hosts.iter().find_map(|host| rest.strip_prefix(host))?;
+    let rest = rest.strip_prefix(section)?;
+
+    let end = rest.find(&['/', '?', '#'][..]).unwrap_or(rest.len());
+    let id = &rest[..end];
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    id.parse().ok()
+}
+
+id_newtype!(
+    /// A store bundle id, as seen alongside apps and packages in licensing data.
+    BundleID(u32),
+    "a bundle id"
+);
+
+id_newtype!(
+    /// A party beacon id, as used by the friends UI party APIs. These live in their own id space
+    /// and are unrelated to SteamIDs and lobby IDs.
+    PartyBeaconID(u64),
+    "a party beacon id"
+);
+
+id_newtype!(
+    /// A handle to a file in Steam's user-generated content storage, such as a Workshop item's
+    /// contents or preview image.
+    UGCID(u64),
+    "a UGC id"
+);
+
+id_newtype!(
+    /// A Steam Workshop item (or other shared file, like a guide or screenshot).
+    ///
+    /// ```
+    /// # use steamid_ng::PublishedFileID;
+    /// let url = "https://steamcommunity.com/sharedfiles/filedetails/?id=2871108184&searchtext=";
+    /// let id = PublishedFileID::from_workshop_url(url).unwrap();
+    /// assert_eq!(id, PublishedFileID::from(2871108184));
+    /// assert_eq!(
+    ///     id.workshop_url(),
+    ///     "https://steamcommunity.com/sharedfiles/filedetails/?id=2871108184"
+    /// );
+    /// ```
+    PublishedFileID(u64),
+    "a published file id"
+);
+
+impl PublishedFileID {
+    /// The canonical `https://steamcommunity.com/sharedfiles/filedetails/?id=<id>` URL.
+    pub fn workshop_url(&self) -> String {
+        format!(
+            "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+            self.0
+        )
+    }
+
+    /// Parses a `steamcommunity.com/sharedfiles/filedetails/?id=...` URL, or its
+    /// `workshop/filedetails/` variant. The scheme and `www.` are optional, and other query
+    /// parameters may come before or after the id.
+    pub fn from_workshop_url(url: &str) -> Option<Self> {
+        let path = community_path(url)?;
+        let rest = path
+            .strip_prefix("sharedfiles/filedetails")
+            .or_else(|| path.strip_prefix("workshop/filedetails"))?;
+        let rest = rest.strip_prefix('/').unwrap_or(rest);
+        let query = rest.strip_prefix('?')?;
+        let query = query.split('#').next().unwrap_or(query);
+
+        let id = query
+            .split('&')
+            .find_map(|param| param.strip_prefix("id="))?;
+        if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        id.parse().ok().map(PublishedFileID)
+    }
+}
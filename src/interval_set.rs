@@ -0,0 +1,119 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::SteamID;
+
+/// A set of SteamIDs stored as disjoint ranges, so that whole blocks of IDs (say, every anonymous
+/// game server in a universe) take up a single entry. Overlapping and adjacent ranges are merged
+/// on insertion.
+///
+/// Ranges are over the steam64, i.e. in the same order as [`SteamID`]'s `Ord`. Combine with
+/// [`SteamID::bounds_for_account_type`] to cover an entire account type:
+///
+/// ```
+/// # use steamid_ng::{SteamID, SteamIDIntervalSet, AccountType, Instance, Universe};
+/// let mut blocked = SteamIDIntervalSet::new();
+/// blocked.insert_range(SteamID::bounds_for_account_type(
+///     Universe::Public,
+///     AccountType::AnonGameServer,
+/// ));
+/// assert!(blocked.contains(SteamID::new(
+///     1234,
+///     Instance::All,
+///     AccountType::AnonGameServer,
+///     Universe::Public
+/// )));
+/// ```
+///
+/// Serializes as a list of `[start, end]` pairs of inclusive bounds.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SteamIDIntervalSet {
+    // start -> inclusive end
+    ranges: BTreeMap<u64, u64>,
+}
+
+impl SteamIDIntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn insert(&mut self, steamid: SteamID) {
+        self.insert_range(steamid..=steamid)
+    }
+
+    /// Inserts every SteamID in `range`. Empty ranges are ignored.
+    pub fn insert_range(&mut self, range: RangeInclusive<SteamID>) {
+        let (mut start, mut end) = (range.start().0, range.end().0);
+        if start > end {
+            return;
+        }
+
+        // Take over a predecessor that overlaps or touches the new range...
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e.saturating_add(1) >= start {
+                start = s;
+                end = end.max(e);
+            }
+        }
+        // ...and every range starting inside or right after it.
+        let absorbed: Vec<(u64, u64)> = self
+            .ranges
+            .range(start..=end.saturating_add(1))
+            .map(|(&s, &e)| (s, e))
+            .collect();
+        for (s, e) in absorbed {
+            end = end.max(e);
+            self.ranges.remove(&s);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    pub fn contains(&self, steamid: SteamID) -> bool {
+        self.ranges
+            .range(..=steamid.0)
+            .next_back()
+            .is_some_and(|(_, &end)| end >= steamid.0)
+    }
+
+    /// Iterates over the stored ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<SteamID>> + '_ {
+        self.ranges
+            .iter()
+            .map(|(&start, &end)| SteamID(start)..=SteamID(end))
+    }
+}
+
+impl Extend<RangeInclusive<SteamID>> for SteamIDIntervalSet {
+    fn extend<T: IntoIterator<Item = RangeInclusive<SteamID>>>(&mut self, iter: T) {
+        for range in iter {
+            self.insert_range(range);
+        }
+    }
+}
+
+impl Serialize for SteamIDIntervalSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter().map(|range| (*range.start(), *range.end())))
+    }
+}
+
+impl<'de> Deserialize<'de> for SteamIDIntervalSet {
+    fn deserialize<D>(deserializer: D) -> Result<SteamIDIntervalSet, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(SteamID, SteamID)>::deserialize(deserializer)?;
+        let mut set = SteamIDIntervalSet::new();
+        set.extend(pairs.into_iter().map(|(start, end)| start..=end));
+        Ok(set)
+    }
+}
@@ -0,0 +1,88 @@
+//! Short invite links, such as `https://s.team/p/hj-qp`.
+//!
+//! The code is the hex account id written with the letters `bcdfghjkmnpqrtvw` in place of the
+//! digits `0-9a-f`, split in half by a dash.
+
+use crate::{
+    AccountType, Cursor, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe,
+};
+
+const ALPHABET: &[u8; 16] = b"bcdfghjkmnpqrtvw";
+
+const URL_PREFIXES: &[&str] = &["https://s.team/p/", "http://s.team/p/", "s.team/p/"];
+
+impl SteamID {
+    /// The invite code of an individual account, or `None` for other account types.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from(76561197960287930);
+    /// assert_eq!(s.invite_code().as_deref(), Some("hj-qp"));
+    /// assert_eq!(s.invite_url().as_deref(), Some("https://s.team/p/hj-qp"));
+    /// ```
+    pub fn invite_code(&self) -> Option<String> {
+        if self.account_type() != AccountType::Individual {
+            return None;
+        }
+
+        let mut code: String = format!("{:x}", self.account_id())
+            .bytes()
+            .map(|hex| {
+                let digit = char::from(hex).to_digit(16).expect("Formatted as hex");
+                char::from(ALPHABET[digit as usize])
+            })
+            .collect();
+        let split = code.len() / 2;
+        if split > 0 {
+            code.insert(split, '-');
+        }
+        Some(code)
+    }
+
+    /// The `https://s.team/p/` link for [`SteamID::invite_code`].
+    pub fn invite_url(&self) -> Option<String> {
+        self.invite_code()
+            .map(|code| format!("https://s.team/p/{}", code))
+    }
+
+    /// Decodes an invite code into an individual account in the public universe. Full invite
+    /// links are accepted too, including ones with a trailing invite token
+    /// (`https://s.team/p/hj-qp/TOKEN`), which is ignored.
+    pub fn from_invite_code(code: &str) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::new(code);
+        if let Some(prefix) = URL_PREFIXES.iter().find(|&&p| code.starts_with(p)) {
+            cursor.pos = prefix.len();
+        }
+
+        let start = cursor.pos;
+        let mut account_id = 0u32;
+        let mut digits = 0;
+        loop {
+            match cursor.peek() {
+                Some(b'-') => (),
+                Some(b'/') | None if digits > 0 => break,
+                Some(byte) => match ALPHABET.iter().position(|&c| c == byte) {
+                    Some(digit) => {
+                        account_id = account_id
+                            .checked_mul(16)
+                            .map(|id| id | digit as u32)
+                            .ok_or_else(|| {
+                                cursor.error_at(SteamIDParseErrorKind::AccountIdOverflow, start)
+                            })?;
+                        digits += 1;
+                    }
+                    None => return Err(cursor.unexpected()),
+                },
+                None => return Err(cursor.unexpected()),
+            }
+            cursor.next();
+        }
+
+        Ok(SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        ))
+    }
+}
@@ -0,0 +1,34 @@
+use std::convert::TryFrom;
+
+use serde_json::Value;
+
+use crate::{SteamID, SteamIDParseError};
+
+/// Accepts the same strings as `FromStr`, unsigned integers, and floats that are exact integers
+/// small enough for an `f64` to hold without rounding (up to 2^53). A steam64 that has been through
+/// a float has usually already lost precision, so those are rejected rather than silently
+/// corrupted.
+impl TryFrom<&Value> for SteamID {
+    type Error = SteamIDParseError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        const MAX_EXACT_F64: f64 = (1u64 << 53) as f64;
+
+        match value {
+            Value::String(s) => s.parse(),
+            Value::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    Ok(n.into())
+                } else {
+                    match n.as_f64() {
+                        Some(f) if (0.0..=MAX_EXACT_F64).contains(&f) && f.fract() == 0.0 => {
+                            Ok((f as u64).into())
+                        }
+                        _ => Err(SteamIDParseError::default()),
+                    }
+                }
+            }
+            _ => Err(SteamIDParseError::default()),
+        }
+    }
+}
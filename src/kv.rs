@@ -0,0 +1,16 @@
+use log::kv::{ToValue, Value};
+
+use crate::SteamID;
+
+/// Captured as the steam64, so structured backends get a number rather than a formatted string.
+impl ToValue for SteamID {
+    fn to_value(&self) -> Value<'_> {
+        Value::from(self.0)
+    }
+}
+
+impl From<SteamID> for Value<'_> {
+    fn from(steamid: SteamID) -> Self {
+        Value::from(steamid.0)
+    }
+}
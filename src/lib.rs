@@ -26,13 +26,119 @@
 //! ```
 //!
 //! Keep in mind that the SteamID type does no validation.
+//!
+//! ## Cargo features
+//!
+//! - `bytes`: `get_steamid`/`put_steamid` extension methods for `bytes::Buf` and `BufMut`.
+//! - `egui`: a `SteamIDEditor` inspector widget for debug overlays and admin tools.
+//! - `ffi`: a C ABI and binding-generator-friendly surface in the [`ffi`] module.
+//! - `friend-code`: CS:GO / CS2 friend codes via `SteamID::friend_code` and `from_friend_code`.
+//! - `fyrox`: Fyrox's `Reflect` and `Visit` for `SteamID`, so it can live in scenes.
+//! - `heapless`: `SteamID::steam2_array` and `steam3_array`, rendering into `heapless::String`s.
+//! - `json`: `TryFrom<&serde_json::Value>` for `SteamID`, for poking at dynamic JSON.
+//! - `log`: `log::kv::ToValue` for `SteamID`, recording the steam64 in structured logs.
+//! - `md5`: BattlEye GUIDs via `SteamID::battleye_guid`.
+//! - `miette`: `miette::Diagnostic` for `SteamIDParseError`, labelling where the input went wrong.
+//! - `pseudonymize`: keyed BLAKE3 pseudonyms for analytics, in the [`pseudonymize`] module.
+//! - `surrealdb`: `SurrealValue` for `SteamID`, so it can be stored in SurrealDB directly.
+//! - `url`: `SteamID::from_url` and `to_url` for working with already parsed `url::Url`s.
+//! - `webapi`: vanity URL resolution and group lists through the Steam Web API, in the
+//!   [`webapi`] module.
 
 #[macro_use]
 extern crate enum_primitive;
 
+mod anon;
+pub mod bans;
+mod batch;
+#[cfg(feature = "md5")]
+mod battleye;
+#[cfg(feature = "bytes")]
+mod buf;
+mod builder;
+mod chat;
+pub mod cohort;
+pub mod commands;
+mod community;
+mod console;
+#[cfg(feature = "miette")]
+mod diagnostic;
+#[cfg(feature = "egui")]
+mod egui_widget;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod find;
+mod formatter;
+#[cfg(feature = "friend-code")]
+mod friend_code;
+#[cfg(feature = "fyrox")]
+mod fyrox;
+mod game_id;
+mod gid;
+#[cfg(feature = "heapless")]
+mod heapless_ext;
+mod ids;
+mod interval_set;
+mod invite;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "log")]
+mod kv;
+mod lines;
+mod lobby;
+mod names;
+mod parse_options;
+mod parts;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "pseudonymize")]
+pub mod pseudonymize;
+mod redacted;
+pub mod serde_helpers;
+pub mod sourcebans;
+pub mod status;
+mod steam_hex;
+mod steam_url;
+pub mod suggest;
+#[cfg(feature = "surrealdb")]
+mod surreal;
+mod typed;
+#[cfg(feature = "url")]
+mod url_ext;
+mod validation;
+pub mod vanity;
+pub mod vdf;
+#[cfg(feature = "webapi")]
+pub mod webapi;
+
+#[cfg(feature = "bytes")]
+pub use buf::{SteamIDBufExt, SteamIDBufMutExt};
+pub use builder::SteamIDBuilder;
+pub use chat::InstanceFlags;
+#[cfg(feature = "egui")]
+pub use egui_widget::SteamIDEditor;
+pub use formatter::{SteamIDFormatter, TemplateError};
+pub use game_id::{GameID, GameIDType};
+pub use gid::{GlobalID, JobID};
+pub use ids::{
+    AccountID, AppID, BundleID, DepotID, PackageID, PartyBeaconID, PublishedFileID, UGCID,
+};
+pub use interval_set::SteamIDIntervalSet;
+pub use lines::SteamIDLines;
+pub use names::ParseNameError;
+pub use parse_options::ParseOptions;
+pub use parts::SteamIDParts;
+pub use redacted::Redacted;
+#[cfg(feature = "surrealdb")]
+pub use surreal::SurrealRepr;
+pub use typed::{AnonGameServerID, ClanID, GameServerID, IndividualID, LobbyID};
+pub use validation::ValidationLevel;
+
 use std::{
     error::Error,
     fmt::{self, Debug, Display, Formatter},
+    num::NonZeroU32,
+    ops::RangeInclusive,
     str::FromStr,
 };
 
@@ -42,15 +148,147 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+/// SteamIDs order by their steam64, which sorts by universe, then account type, then instance, then
+/// account id.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize)]
 pub struct SteamID(u64);
 
-fn digit_from_ascii(byte: u8) -> Option<u8> {
-    if (b'0'..=b'9').contains(&byte) {
-        Some(byte - b'0')
-    } else {
-        None
+// Walks the bytes of an ID, turning anything unexpected into the matching parse error. The bytes
+// don't have to be UTF-8
+struct Cursor<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(s: &'a str) -> Self {
+        Cursor::from_bytes(s.as_bytes())
+    }
+
+    fn from_bytes(s: &'a [u8]) -> Self {
+        Cursor { s, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn error_at(&self, kind: SteamIDParseErrorKind, pos: usize) -> SteamIDParseError {
+        SteamIDParseError {
+            position: Some(pos),
+            found: self.s.get(pos..).and_then(first_char),
+            ..SteamIDParseError::new(kind)
+        }
+    }
+
+    fn unexpected(&self) -> SteamIDParseError {
+        let kind = match self.peek() {
+            Some(_) => SteamIDParseErrorKind::UnexpectedCharacter,
+            None => SteamIDParseErrorKind::UnexpectedEnd,
+        };
+        self.error_at(kind, self.pos)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), SteamIDParseError> {
+        if self.peek() != Some(expected) {
+            return Err(self.unexpected());
+        }
+        self.pos += 1;
+        Ok(())
     }
+
+    fn end(&self) -> Result<(), SteamIDParseError> {
+        match self.peek() {
+            Some(_) => Err(self.unexpected()),
+            None => Ok(()),
+        }
+    }
+
+    fn digit(&mut self) -> Result<u8, SteamIDParseError> {
+        match self.peek() {
+            Some(byte) if byte.is_ascii_digit() => {
+                self.pos += 1;
+                Ok(byte - b'0')
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    // Reads a run of at least one digit, returning the value and the number of digits. Values
+    // above max are reported as overflow, pointing at the start of the number.
+    fn number(
+        &mut self,
+        max: u64,
+        overflow: SteamIDParseErrorKind,
+    ) -> Result<(u64, usize), SteamIDParseError> {
+        let start = self.pos;
+        let mut value = u64::from(self.digit()?);
+        while let Some(byte) = self.peek().filter(u8::is_ascii_digit) {
+            self.pos += 1;
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(u64::from(byte - b'0')))
+                .filter(|&v| v <= max)
+                .ok_or_else(|| self.error_at(overflow, start))?;
+        }
+
+        Ok((value, self.pos - start))
+    }
+}
+
+// Decodes the character `bytes` start with, or U+FFFD if they don't start with valid UTF-8
+fn first_char(bytes: &[u8]) -> Option<char> {
+    let len = bytes.len().min(4);
+    let valid = match std::str::from_utf8(&bytes[..len]) {
+        Ok(valid) => valid,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).expect("Valid up to here"),
+    };
+    match valid.chars().next() {
+        None if !bytes.is_empty() => Some(char::REPLACEMENT_CHARACTER),
+        c => c,
+    }
+}
+
+// Writes into a byte slice, failing once it's full
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+// Declares a pair of SteamID constants holding the ends of an account type's public range
+macro_rules! public_bounds {
+    ($($min:ident, $max:ident => $account_type:ident;)*) => {
+        $(
+            #[doc = concat!(
+                "The smallest steam64 of a public `", stringify!($account_type), "`, whatever ",
+                "its instance and account id. See [`SteamID::bounds_for_account_type`]."
+            )]
+            pub const $min: u64 = Self::public_min(AccountType::$account_type);
+            #[doc = concat!(
+                "The largest steam64 of a public `", stringify!($account_type), "`, whatever ",
+                "its instance and account id."
+            )]
+            pub const $max: u64 = Self::public_max(AccountType::$account_type);
+        )*
+    };
 }
 
 impl SteamID {
@@ -59,6 +297,11 @@ impl SteamID {
         (self.0 & 0xFFFFFFFF) as u32
     }
 
+    /// Returns the account id, or `None` if it is zero (as it is for blank and anonymous IDs).
+    pub fn account_id_non_zero(&self) -> Option<NonZeroU32> {
+        NonZeroU32::new(self.account_id())
+    }
+
     pub fn set_account_id(&mut self, account_id: u32) {
         self.0 &= 0xFFFFFFFF00000000;
         self.0 |= u64::from(account_id);
@@ -73,6 +316,27 @@ impl SteamID {
         self.0 |= (instance as u64) << 32;
     }
 
+    /// The raw 20-bit instance, including values [`Instance`] has no variant for, such as the
+    /// per-session instances Steam hands out to anonymous game servers.
+    pub fn instance_value(&self) -> u32 {
+        (self.0 >> 32) as u32 & Self::ACCOUNT_INSTANCE_MASK
+    }
+
+    /// Sets the raw instance. Only the low 20 bits of `instance` are kept.
+    ///
+    /// ```
+    /// # use steamid_ng::{Instance, SteamID};
+    /// let mut s = SteamID::from_steam3("[A:1:1234:0]").unwrap();
+    /// s.set_instance_value(1337);
+    /// assert_eq!(s.instance_value(), 1337);
+    /// assert_eq!(s.instance(), Instance::Invalid);
+    /// assert_eq!(s.steam3(), "[A:1:1234:1337]");
+    /// ```
+    pub fn set_instance_value(&mut self, instance: u32) {
+        self.0 &= 0xFFF00000FFFFFFFF;
+        self.0 |= u64::from(instance & Self::ACCOUNT_INSTANCE_MASK) << 32;
+    }
+
     pub fn account_type(&self) -> AccountType {
         AccountType::from_u64((self.0 >> 52) & 0xF).unwrap_or(AccountType::Invalid)
     }
@@ -82,6 +346,18 @@ impl SteamID {
         self.0 |= (account_type as u64) << 52;
     }
 
+    /// The raw 4-bit account type. Unlike [`SteamID::account_type`], this tells apart the types
+    /// past [`AccountType::AnonUser`] that Valve hasn't assigned yet.
+    pub fn account_type_value(&self) -> u8 {
+        ((self.0 >> Self::ACCOUNT_TYPE_SHIFT) as u32 & Self::ACCOUNT_TYPE_MASK) as u8
+    }
+
+    /// Sets the raw account type. Only the low 4 bits of `account_type` are kept.
+    pub fn set_account_type_value(&mut self, account_type: u8) {
+        self.0 &= 0xFF0FFFFFFFFFFFFF;
+        self.0 |= u64::from(u32::from(account_type) & Self::ACCOUNT_TYPE_MASK) << 52;
+    }
+
     pub fn universe(&self) -> Universe {
         Universe::from_u64((self.0 >> 56) & 0xFF).unwrap_or(Universe::Invalid)
     }
@@ -91,73 +367,620 @@ impl SteamID {
         self.0 |= (universe as u64) << 56;
     }
 
+    /// The raw 8-bit universe, including ones [`Universe`] has no variant for.
+    pub fn universe_value(&self) -> u8 {
+        (self.0 >> Self::UNIVERSE_SHIFT) as u8
+    }
+
+    pub fn set_universe_value(&mut self, universe: u8) {
+        self.0 &= 0x00FFFFFFFFFFFFFF;
+        self.0 |= u64::from(universe) << 56;
+    }
+
+    /// A copy of this ID with the account id replaced. Unlike [`SteamID::set_account_id`] and the
+    /// other setters, the `with_*` methods can be chained and used in constants:
+    ///
+    /// ```
+    /// # use steamid_ng::{AccountType, Instance, SteamID, Universe};
+    /// const BETA_WEB: SteamID = SteamID::nil()
+    ///     .with_account_id(22202)
+    ///     .with_instance(Instance::Web)
+    ///     .with_account_type(AccountType::Individual)
+    ///     .with_universe(Universe::Beta);
+    /// assert_eq!(BETA_WEB.steam3(), "[U:2:22202:4]");
+    /// ```
+    pub const fn with_account_id(self, account_id: u32) -> Self {
+        SteamID((self.0 & 0xFFFFFFFF00000000) | account_id as u64)
+    }
+
+    pub const fn with_instance(self, instance: Instance) -> Self {
+        SteamID((self.0 & 0xFFF00000FFFFFFFF) | (instance as u64) << 32)
+    }
+
+    pub const fn with_account_type(self, account_type: AccountType) -> Self {
+        SteamID((self.0 & 0xFF0FFFFFFFFFFFFF) | (account_type as u64) << 52)
+    }
+
+    pub const fn with_universe(self, universe: Universe) -> Self {
+        SteamID((self.0 & 0x00FFFFFFFFFFFFFF) | (universe as u64) << 56)
+    }
+
+    /// Puts the fields together as they are, without checking that Steam would accept the
+    /// combination. The same as [`SteamID::new_unchecked`]; see [`SteamID::try_new`] for one that
+    /// checks.
     pub fn new(
         account_id: u32,
         instance: Instance,
         account_type: AccountType,
         universe: Universe,
     ) -> Self {
-        #[cfg_attr(rustfmt, rustfmt_skip)]
-        Self::from(
-            u64::from(account_id)         | ((instance as u64) << 32) |
+        Self::new_unchecked(account_id, instance, account_type, universe)
+    }
+
+    /// Like [`SteamID::new`], but rejects combinations [`SteamID::is_valid`] doesn't accept, such
+    /// as a clan with the desktop instance or an individual without an account id.
+    ///
+    /// ```
+    /// # use steamid_ng::{AccountType, Instance, SteamID, SteamIDParseErrorKind, Universe};
+    /// let user = SteamID::try_new(22202, Instance::Desktop, AccountType::Individual, Universe::Public);
+    /// assert_eq!(user, Ok(SteamID::from(76561197960287930)));
+    ///
+    /// let clan = SteamID::try_new(4, Instance::Desktop, AccountType::Clan, Universe::Public);
+    /// assert_eq!(clan.unwrap_err().kind(), SteamIDParseErrorKind::InvalidInstance);
+    /// ```
+    pub fn try_new(
+        account_id: u32,
+        instance: Instance,
+        account_type: AccountType,
+        universe: Universe,
+    ) -> Result<Self, SteamIDParseError> {
+        Self::try_new_with(
+            account_id,
+            instance,
+            account_type,
+            universe,
+            ValidationLevel::Semantic,
+        )
+    }
+
+    /// Puts the fields together without any checks. Usable in constants.
+    #[rustfmt::skip]
+    pub const fn new_unchecked(
+        account_id: u32,
+        instance: Instance,
+        account_type: AccountType,
+        universe: Universe,
+    ) -> Self {
+        SteamID(
+            account_id as u64             | ((instance as u64) << 32) |
             ((account_type as u64) << 52) | ((universe as u64) << 56),
         )
     }
 
+    /// Like `From<u64>`, but rejects IDs whose universe or instance Steam doesn't know about.
+    /// Instances are all, desktop, console or web, which chats may combine with the chat flags.
+    /// Anonymous game servers may have any instance, since Steam numbers them by it. (This
+    /// can't be a `TryFrom<u64>` impl, since that would conflict with `From<u64>`.)
+    ///
+    /// Every account type is accepted, so IDs of types Valve adds later keep working. Those read
+    /// as [`AccountType::Invalid`]; [`SteamID::account_type_value`] has the actual number.
+    pub fn from_steam64(steam64: u64) -> Result<Self, SteamIDParseError> {
+        if Universe::from_u64(steam64 >> 56).is_none() {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidUniverse,
+            ));
+        }
+        Self::from_steam64_any_universe(steam64)
+    }
+
+    /// Like [`SteamID::from_steam64`], but accepts universes Steam doesn't know about, for
+    /// callers that only care about the account id and would rather not reject IDs from partner
+    /// or future universes. Those read as [`Universe::Invalid`]; [`SteamID::universe_value`] has
+    /// the actual number.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, Universe};
+    /// let s = SteamID::from_steam64_any_universe(0x0710_0001_0000_56BA).unwrap();
+    /// assert_eq!(s.universe(), Universe::Invalid);
+    /// assert_eq!(s.universe_value(), 7);
+    /// assert_eq!(s.account_id(), 22202);
+    /// assert!(SteamID::from_steam64(s.into()).is_err());
+    /// ```
+    pub fn from_steam64_any_universe(steam64: u64) -> Result<Self, SteamIDParseError> {
+        let steamid = SteamID(steam64);
+        if !Self::is_known_instance(steamid.account_type_value(), steamid.instance_value()) {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidInstance,
+            ));
+        }
+
+        Ok(steamid)
+    }
+
+    fn is_known_instance(account_type: u8, instance: u32) -> bool {
+        const CHAT_FLAGS: u32 = SteamID::CHAT_INSTANCE_FLAG_CLAN
+            | SteamID::CHAT_INSTANCE_FLAG_LOBBY
+            | SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY;
+
+        if instance > Self::ACCOUNT_INSTANCE_MASK {
+            return false;
+        }
+        let instance = match AccountType::from_u8(account_type) {
+            Some(AccountType::AnonGameServer) => return true,
+            Some(AccountType::Chat) => instance & !CHAT_FLAGS,
+            _ => instance,
+        };
+        matches!(instance, 0 | 1 | 2 | 4)
+    }
+
+    /// Sets the account type from its raw value, such as one decoded from a CM message. Like
+    /// [`SteamID::from_steam64`], this accepts any value that fits in the 4-bit field, including
+    /// those Valve hasn't assigned yet. On error the ID is left unchanged.
+    pub fn try_set_account_type(&mut self, account_type: u8) -> Result<(), SteamIDParseError> {
+        if u32::from(account_type) > Self::ACCOUNT_TYPE_MASK {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidAccountType,
+            ));
+        }
+        self.set_account_type_value(account_type);
+        Ok(())
+    }
+
+    /// Sets the universe from its raw value, rejecting universes Steam doesn't know about. On
+    /// error the ID is left unchanged.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, Universe};
+    /// let mut s = SteamID::from(76561197960287930);
+    /// s.try_set_universe(2).unwrap();
+    /// assert_eq!(s.universe(), Universe::Beta);
+    /// assert!(s.try_set_universe(9).is_err());
+    /// assert_eq!(s.universe(), Universe::Beta);
+    /// ```
+    pub fn try_set_universe(&mut self, universe: u8) -> Result<(), SteamIDParseError> {
+        let universe = Universe::from_u8(universe)
+            .ok_or_else(|| SteamIDParseError::new(SteamIDParseErrorKind::InvalidUniverse))?;
+        self.set_universe(universe);
+        Ok(())
+    }
+
+    /// Sets the instance from its raw value, accepting the instances [`SteamID::from_steam64`]
+    /// does for this ID's account type. On error the ID is left unchanged.
+    pub fn try_set_instance(&mut self, instance: u32) -> Result<(), SteamIDParseError> {
+        if !Self::is_known_instance(self.account_type_value(), instance) {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidInstance,
+            ));
+        }
+        self.set_instance_value(instance);
+        Ok(())
+    }
+
+    /// The all-zero ID, which Valve services send in place of one that isn't known yet, such as
+    /// before logon. It's also what `SteamID::default()` gives. It isn't
+    /// [valid](SteamID::is_valid), having both an invalid account type and universe.
+    pub const fn nil() -> Self {
+        SteamID(0)
+    }
+
+    /// Whether every bit is zero, as in [`SteamID::nil`]. IDs with only the account id zeroed,
+    /// such as [`SteamID::anon_user`], aren't nil.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, Universe};
+    /// assert!(SteamID::default().is_nil());
+    /// assert!(!SteamID::anon_user(Universe::Public).is_nil());
+    /// ```
+    pub const fn is_nil(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether Steam would consider this a real ID, following the rules of Valve's
+    /// `CSteamID::IsValid` (and SteamKit's): the account type and universe have to be known and
+    /// not `Invalid`, individuals need an account id and an instance no higher than
+    /// [`Instance::Web`], clans need an account id and no instance, and persistent game servers
+    /// need an account id.
+    ///
+    /// This is stricter than [`SteamID::from_steam64`], which only checks each field's range.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// assert!(SteamID::from(76561197960287930).is_valid());
+    /// assert!(!SteamID::from_steam3("[U:1:0]").unwrap().is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        self.validity_error().is_none()
+    }
+
+    /// The form Steam uses for this account when telling identities apart: individuals get the
+    /// desktop instance that steam64s and profile URLs carry, clans lose their instance, and
+    /// console users get the console instance. Other IDs are returned as they are, since their
+    /// instance is part of what they point to.
+    ///
+    /// Normalize IDs before using them as keys, so the same user logged on through the web
+    /// doesn't end up in a `HashSet` twice:
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let web = SteamID::from_steam3("[U:1:22202:4]").unwrap();
+    /// assert_ne!(web, SteamID::from(76561197960287930));
+    /// assert_eq!(web.normalize(), SteamID::from(76561197960287930));
+    /// ```
+    pub fn normalize(&self) -> Self {
+        let instance = match self.account_type() {
+            AccountType::Individual => Instance::Desktop,
+            AccountType::Clan => Instance::All,
+            AccountType::ConsoleUser => Instance::Console,
+            _ => return *self,
+        };
+        let mut normalized = *self;
+        normalized.set_instance(instance);
+        normalized
+    }
+
+    /// Whether both IDs are the same account, whatever their instances. IDs from the web API,
+    /// CMs and game servers often differ only there.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let web = SteamID::from_steam3("[U:1:22202:4]").unwrap();
+    /// assert!(web.eq_ignoring_instance(&SteamID::from(76561197960287930)));
+    /// ```
+    pub fn eq_ignoring_instance(&self, other: &SteamID) -> bool {
+        let mask = !(u64::from(Self::ACCOUNT_INSTANCE_MASK) << Self::INSTANCE_SHIFT);
+        self.0 & mask == other.0 & mask
+    }
+
+    /// Like [`SteamID::eq_ignoring_instance`], but also ignores the universe, for comparing a
+    /// beta or dev universe ID against its public counterpart.
+    pub fn eq_ignoring_instance_and_universe(&self, other: &SteamID) -> bool {
+        let mask = !(u64::from(Self::ACCOUNT_INSTANCE_MASK) << Self::INSTANCE_SHIFT)
+            & !(u64::from(Self::UNIVERSE_MASK) << Self::UNIVERSE_SHIFT);
+        self.0 & mask == other.0 & mask
+    }
+
+    fn validity_error(&self) -> Option<SteamIDParseErrorKind> {
+        let account_type = match AccountType::from_u64((self.0 >> 52) & 0xF) {
+            None | Some(AccountType::Invalid) => {
+                return Some(SteamIDParseErrorKind::InvalidAccountType)
+            }
+            Some(account_type) => account_type,
+        };
+        if matches!(
+            Universe::from_u64(self.0 >> 56),
+            None | Some(Universe::Invalid)
+        ) {
+            return Some(SteamIDParseErrorKind::InvalidUniverse);
+        }
+
+        let instance = self.instance_value();
+        let instance_ok = match account_type {
+            AccountType::Individual => instance <= Instance::Web as u32,
+            AccountType::Clan => instance == 0,
+            _ => true,
+        };
+        let needs_account_id = matches!(
+            account_type,
+            AccountType::Individual | AccountType::Clan | AccountType::GameServer
+        );
+        if !instance_ok {
+            Some(SteamIDParseErrorKind::InvalidInstance)
+        } else if needs_account_id && self.account_id() == 0 {
+            Some(SteamIDParseErrorKind::MissingAccountId)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_individual(&self) -> bool {
+        self.account_type() == AccountType::Individual
+    }
+
+    pub fn is_clan(&self) -> bool {
+        self.account_type() == AccountType::Clan
+    }
+
+    /// Whether this is a game server, persistent or anonymous.
+    pub fn is_game_server(&self) -> bool {
+        matches!(
+            self.account_type(),
+            AccountType::GameServer | AccountType::AnonGameServer
+        )
+    }
+
+    /// The range holding every SteamID of `account_type` in `universe`, whatever its instance
+    /// and account id. Sorted collections can be range-scanned with it:
+    ///
+    /// ```
+    /// # use std::collections::BTreeSet;
+    /// # use steamid_ng::{SteamID, AccountType, Universe};
+    /// let ids: BTreeSet<SteamID> = [76561197960287930, 103582791429521412]
+    ///     .iter()
+    ///     .map(|&id| SteamID::from(id))
+    ///     .collect();
+    /// let clans = ids.range(SteamID::bounds_for_account_type(Universe::Public, AccountType::Clan));
+    /// assert_eq!(clans.count(), 1);
+    /// ```
+    pub const fn bounds_for_account_type(
+        universe: Universe,
+        account_type: AccountType,
+    ) -> RangeInclusive<SteamID> {
+        let start = ((universe as u64) << 56) | ((account_type as u64) << 52);
+        RangeInclusive::new(SteamID(start), SteamID(start | 0x000FFFFFFFFFFFFF))
+    }
+
+    // The ends of bounds_for_account_type in the public universe
+    const fn public_min(account_type: AccountType) -> u64 {
+        Self::bounds_for_account_type(Universe::Public, account_type)
+            .start()
+            .0
+    }
+
+    const fn public_max(account_type: AccountType) -> u64 {
+        Self::bounds_for_account_type(Universe::Public, account_type)
+            .end()
+            .0
+    }
+
+    /// The range holding every SteamID in `universe`. See [`SteamID::bounds_for_account_type`].
+    pub fn bounds_for_universe(universe: Universe) -> RangeInclusive<SteamID> {
+        let start = (universe as u64) << 56;
+        SteamID(start)..=SteamID(start | 0x00FFFFFFFFFFFFFF)
+    }
+
+    /// The steam64 of `[U:1:0]`. Individuals in the public universe have steam64s of this plus
+    /// their account id, which is how most code converting between the two does it.
+    pub const PUBLIC_INDIVIDUAL_BASE: u64 = 76561197960265728;
+    /// The steam64 of `[g:1:0]`, which clans' account ids are added to the same way.
+    pub const PUBLIC_CLAN_BASE: u64 = 103582791429521408;
+
+    public_bounds! {
+        PUBLIC_INDIVIDUAL_MIN, PUBLIC_INDIVIDUAL_MAX => Individual;
+        PUBLIC_MULTISEAT_MIN, PUBLIC_MULTISEAT_MAX => Multiseat;
+        PUBLIC_GAME_SERVER_MIN, PUBLIC_GAME_SERVER_MAX => GameServer;
+        PUBLIC_ANON_GAME_SERVER_MIN, PUBLIC_ANON_GAME_SERVER_MAX => AnonGameServer;
+        PUBLIC_PENDING_MIN, PUBLIC_PENDING_MAX => Pending;
+        PUBLIC_CONTENT_SERVER_MIN, PUBLIC_CONTENT_SERVER_MAX => ContentServer;
+        PUBLIC_CLAN_MIN, PUBLIC_CLAN_MAX => Clan;
+        PUBLIC_CHAT_MIN, PUBLIC_CHAT_MAX => Chat;
+        PUBLIC_CONSOLE_USER_MIN, PUBLIC_CONSOLE_USER_MAX => ConsoleUser;
+        PUBLIC_ANON_USER_MIN, PUBLIC_ANON_USER_MAX => AnonUser;
+    }
+
+    /// The bits of the steam64 that hold the account id, `k_unSteamAccountIDMask` in the Steamworks
+    /// SDK.
+    pub const ACCOUNT_ID_MASK: u32 = 0xFFFF_FFFF;
+    /// Where the instance starts in the steam64.
+    pub const INSTANCE_SHIFT: u32 = 32;
+    /// The bits of the instance, once shifted down, `k_unSteamAccountInstanceMask`.
+    pub const ACCOUNT_INSTANCE_MASK: u32 = 0x000F_FFFF;
+    /// Where the account type starts in the steam64.
+    pub const ACCOUNT_TYPE_SHIFT: u32 = 52;
+    /// The bits of the account type, once shifted down.
+    pub const ACCOUNT_TYPE_MASK: u32 = 0xF;
+    /// Where the universe starts in the steam64.
+    pub const UNIVERSE_SHIFT: u32 = 56;
+    /// The bits of the universe, once shifted down.
+    pub const UNIVERSE_MASK: u32 = 0xFF;
+
+    /// The 32-bit "community ID" of an individual account, as old forum software and game
+    /// servers store it, or `None` for other account types. It's `Z * 2 + Y` for the steam2 ID
+    /// `STEAM_X:Y:Z`, so the low bit is the auth server and the rest is `Z`. That happens to be
+    /// the account id, but unlike [`SteamID::account_id`] this says nothing about other account
+    /// types, whose steam2 form isn't split like that.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from_steam2("STEAM_1:0:11101").unwrap();
+    /// assert_eq!(s.steam32(), Some(22202));
+    /// assert_eq!(SteamID::from_steam32(22202), s);
+    /// ```
+    pub fn steam32(&self) -> Option<u32> {
+        if self.account_type() != AccountType::Individual {
+            return None;
+        }
+
+        Some(self.account_id())
+    }
+
+    /// The individual account in the public universe with the 32-bit community ID `steam32`. See
+    /// [`SteamID::steam32`].
+    pub fn from_steam32(steam32: u32) -> Self {
+        Self::new(
+            steam32,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        )
+    }
+
     pub fn steam2(&self) -> String {
+        self.steam2_with(Steam2Format::OrangeBox)
+    }
+
+    /// Renders a steam2 ID with the universe digit `format` calls for.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, Steam2Format};
+    /// let s = SteamID::from(76561197960287930);
+    /// assert_eq!(s.steam2_with(Steam2Format::GoldSrc), "STEAM_0:0:11101");
+    /// assert_eq!(s.steam2_with(Steam2Format::OrangeBox), "STEAM_1:0:11101");
+    /// ```
+    pub fn steam2_with(&self, format: Steam2Format) -> String {
+        let mut steam2 = String::new();
+        self.write_steam2_with(format, &mut steam2)
+            .expect("Writing to a String can't fail");
+        steam2
+    }
+
+    /// Like [`SteamID::steam2`], but fails instead of falling back to the steam64 for account
+    /// types that have no steam2 form, which is everything but individuals (and invalid IDs).
+    ///
+    /// ```
+    /// # use steamid_ng::{AccountType, SteamID};
+    /// let mut s = SteamID::from(76561197960287930);
+    /// assert_eq!(s.try_steam2().unwrap(), "STEAM_1:0:11101");
+    /// s.set_account_type(AccountType::Clan);
+    /// assert_eq!(s.try_steam2().unwrap_err().account_type(), AccountType::Clan);
+    /// ```
+    pub fn try_steam2(&self) -> Result<String, Steam2RenderError> {
         match self.account_type() {
-            AccountType::Individual | AccountType::Invalid => {
+            AccountType::Individual | AccountType::Invalid => Ok(self.steam2()),
+            account_type => Err(Steam2RenderError { account_type }),
+        }
+    }
+
+    /// Like [`SteamID::steam2`], but fails if parsing the result with [`SteamID::from_steam2`]
+    /// wouldn't give back this exact ID, as happens for any instance but desktop, and for
+    /// everything but individuals.
+    ///
+    /// ```
+    /// # use steamid_ng::{Instance, SteamID};
+    /// let mut s = SteamID::from(76561197960287930);
+    /// assert_eq!(s.steam2_strict().unwrap(), "STEAM_1:0:11101");
+    /// s.set_instance(Instance::Web);
+    /// assert!(s.steam2_strict().is_err());
+    /// ```
+    pub fn steam2_strict(&self) -> Result<String, LossyRenderError> {
+        let steam2 = self.steam2();
+        self.check_round_trip(&steam2, "steam2", Self::from_steam2_helper)?;
+        Ok(steam2)
+    }
+
+    /// Writes [`SteamID::steam2`] to `w`, without allocating.
+    pub fn write_steam2<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        self.write_steam2_with(Steam2Format::OrangeBox, w)
+    }
+
+    /// The longest [`SteamID::steam2_with`] can be in any format, a steam64 included.
+    pub const STEAM2_MAX_LEN: usize = "18446744073709551615".len();
+
+    /// Writes [`SteamID::steam2_with`] to `w`, without allocating.
+    pub fn write_steam2_with<W: fmt::Write + ?Sized>(
+        &self,
+        format: Steam2Format,
+        w: &mut W,
+    ) -> fmt::Result {
+        match (format, self.account_type()) {
+            (Steam2Format::SteamKit, AccountType::Pending) => w.write_str("STEAM_ID_PENDING"),
+            (Steam2Format::SteamKit, AccountType::Invalid) => w.write_str("UNKNOWN"),
+            (_, AccountType::Individual | AccountType::Invalid) => {
+                let universe = match format {
+                    Steam2Format::GoldSrc => 0,
+                    Steam2Format::OrangeBox | Steam2Format::SteamKit => self.universe() as u64,
+                };
                 let id = self.account_id();
-                format!("STEAM_{}:{}:{}", self.universe() as u64, id & 1, id >> 1)
+                write!(w, "STEAM_{}:{}:{}", universe, id & 1, id >> 1)
             }
-            _ => self.0.to_string(),
+            _ => write!(w, "{}", self.0),
         }
     }
 
+    /// Parses a steam2 ID such as `STEAM_1:0:11101`. Use [`suggest::steam2`] to offer a
+    /// correction for near misses like `STEAM1:0:11101`.
     pub fn from_steam2(steam2: &str) -> Result<Self, SteamIDParseError> {
-        Self::from_steam2_helper(steam2).ok_or(SteamIDParseError {})
+        Self::from_steam2_helper(steam2)
+    }
+
+    /// Like [`SteamID::from_steam2`], but also accepts the placeholders GoldSrc and Source
+    /// engines print in place of a steam2 ID:
+    ///
+    /// - `STEAM_ID_PENDING` (not yet authenticated) is a [`AccountType::Pending`] ID in the public
+    ///   universe, with account id 0.
+    /// - `STEAM_ID_LAN` and `UNKNOWN` (no Steam authentication at all) are the all-invalid
+    ///   `SteamID::from(0)`.
+    /// - `BOT` is an [`AccountType::AnonUser`] ID in the public universe, with account id 0.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, AccountType};
+    /// let pending = SteamID::from_steam2_extended("STEAM_ID_PENDING").unwrap();
+    /// assert_eq!(pending.account_type(), AccountType::Pending);
+    /// assert_eq!(SteamID::from_steam2_extended("STEAM_ID_LAN"), Ok(SteamID::from(0)));
+    /// ```
+    pub fn from_steam2_extended(steam2: &str) -> Result<Self, SteamIDParseError> {
+        match steam2 {
+            "STEAM_ID_PENDING" => Ok(Self::new(
+                0,
+                Instance::All,
+                AccountType::Pending,
+                Universe::Public,
+            )),
+            "STEAM_ID_LAN" | "UNKNOWN" => Ok(SteamID(0)),
+            "BOT" => Ok(Self::new(
+                0,
+                Instance::All,
+                AccountType::AnonUser,
+                Universe::Public,
+            )),
+            _ => Self::from_steam2(steam2),
+        }
+    }
+
+    /// Like [`SteamID::from_steam2`], but rejects universe 0 with
+    /// [`SteamIDParseErrorKind::UniverseZero`] instead of reading it as public. Games before the
+    /// Orange Box printed 0 for the public universe, so this flags legacy data.
+    pub fn from_steam2_strict(steam2: &str) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam2(steam2.as_bytes(), true)
+    }
+
+    /// Like [`SteamID::from_steam2`], but for an ID still in a byte buffer, such as a packet.
+    /// The bytes aren't copied or checked for UTF-8 first; any that aren't ASCII are simply
+    /// unexpected.
+    pub fn from_steam2_bytes(steam2: &[u8]) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam2(steam2, false)
+    }
+
+    fn from_steam2_helper(steam2: &str) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam2(steam2.as_bytes(), false)
     }
 
     // Parses id in the format of:
     // ^STEAM_(universe:[0-4]):(auth_server:[0-1]):(account_id:[0-9]{1,10})$
-    fn from_steam2_helper(steam2: &str) -> Option<Self> {
-        let chunk = steam2.strip_prefix("STEAM_")?;
-        let mut bytes = chunk.bytes();
+    // Universe 0 is rejected if `strict`, and read as public otherwise
+    fn parse_steam2(steam2: &[u8], strict: bool) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::from_bytes(steam2);
+        if !steam2.starts_with(b"STEAM_") {
+            return Err(cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0));
+        }
+        cursor.pos = "STEAM_".len();
 
-        let mut universe: Universe = bytes
-            .next()
-            .and_then(|b| Universe::from_u64(u64::from(digit_from_ascii(b)?)))?;
+        let universe_pos = cursor.pos;
+        let mut universe = Universe::from_u8(cursor.digit()?)
+            .ok_or_else(|| cursor.error_at(SteamIDParseErrorKind::InvalidUniverse, universe_pos))?;
         // Apparently, games before orange box used to display as 0 incorrectly
         // This is only an issue with steam2 ids
         if let Universe::Invalid = universe {
+            if strict {
+                return Err(cursor.error_at(SteamIDParseErrorKind::UniverseZero, universe_pos));
+            }
             universe = Universe::Public;
         }
 
-        if bytes.next() != Some(b':') {
-            return None;
-        }
-
-        let auth_server: u32 = match bytes.next()? {
-            b'0' => Some(0),
-            b'1' => Some(1),
-            _ => None,
-        }?;
+        cursor.expect(b':')?;
+        let auth_server_pos = cursor.pos;
+        let auth_server = match cursor.digit()? {
+            digit @ (0 | 1) => u32::from(digit),
+            _ => {
+                return Err(
+                    cursor.error_at(SteamIDParseErrorKind::UnexpectedCharacter, auth_server_pos)
+                )
+            }
+        };
+        cursor.expect(b':')?;
 
-        if bytes.next() != Some(b':') {
-            return None;
+        let account_id_pos = cursor.pos;
+        // The account id is twice this plus the auth server bit, so it gets one bit fewer
+        let (account_id, digits) = cursor.number(
+            u64::from(u32::MAX >> 1),
+            SteamIDParseErrorKind::AccountIdOverflow,
+        )?;
+        if digits > 10 {
+            return Err(cursor.error_at(SteamIDParseErrorKind::AccountIdOverflow, account_id_pos));
         }
+        cursor.end()?;
+        let account_id = (account_id as u32) << 1 | auth_server;
 
-        if bytes.len() > 10 {
-            return None;
-        }
-        let mut account_id = u32::from(digit_from_ascii(bytes.next()?)?);
-        for b in bytes {
-            account_id = account_id.checked_mul(10)?;
-            account_id = account_id.checked_add(u32::from(digit_from_ascii(b)?))?;
-        }
-        let account_id = account_id << 1 | auth_server;
-
-        Some(Self::new(
+        Ok(Self::new(
             account_id,
             Instance::Desktop,
             AccountType::Individual,
@@ -165,121 +988,411 @@ impl SteamID {
         ))
     }
 
+    /// The longest [`SteamID::steam3`] can be, so a buffer this size always fits
+    /// [`SteamID::render_steam3_into`].
+    pub const STEAM3_MAX_LEN: usize = "[A:4:4294967295:1048575]".len();
+
     pub fn steam3(&self) -> String {
-        let instance = self.instance();
+        let mut steam3 = String::new();
+        self.write_steam3(&mut steam3)
+            .expect("Writing to a String can't fail");
+        steam3
+    }
+
+    /// Like [`SteamID::steam3`], but fails if parsing the result with [`SteamID::from_steam3`]
+    /// wouldn't give back this exact ID, such as for a chat with flags no type character stands
+    /// for or an instance that isn't rendered.
+    ///
+    /// ```
+    /// # use steamid_ng::{Instance, SteamID};
+    /// let mut s = SteamID::from_steam3("[g:1:4]").unwrap();
+    /// assert_eq!(s.steam3_strict().unwrap(), "[g:1:4]");
+    /// s.set_instance(Instance::Web);
+    /// assert!(s.steam3_strict().is_err());
+    /// ```
+    pub fn steam3_strict(&self) -> Result<String, LossyRenderError> {
+        let steam3 = self.steam3();
+        self.check_round_trip(&steam3, "steam3", Self::from_steam3_helper)?;
+        Ok(steam3)
+    }
+
+    fn check_round_trip(
+        &self,
+        rendered: &str,
+        format: &'static str,
+        parse: fn(&str) -> Result<SteamID, SteamIDParseError>,
+    ) -> Result<(), LossyRenderError> {
+        if parse(rendered) == Ok(*self) {
+            Ok(())
+        } else {
+            Err(LossyRenderError {
+                steamid: *self,
+                format,
+            })
+        }
+    }
+
+    /// [`SteamID::steam3`] without the surrounding brackets, e.g. `U:1:22202`, as SourceMod and
+    /// many config files print it.
+    pub fn steam3_bare(&self) -> String {
+        let mut steam3 = String::new();
+        self.write_steam3_bare(&mut steam3)
+            .expect("Writing to a String can't fail");
+        steam3
+    }
+
+    /// Writes [`SteamID::steam3`] to `w`, without allocating.
+    pub fn write_steam3<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        w.write_char('[')?;
+        self.write_steam3_bare(w)?;
+        w.write_char(']')
+    }
+
+    /// Renders [`SteamID::steam3`] into the start of `buf`, such as a packet being assembled,
+    /// and returns how many bytes it took.
+    ///
+    /// # Panics
+    ///
+    /// If `buf` is too short. [`SteamID::STEAM3_MAX_LEN`] bytes are always enough.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let mut buf = [0; SteamID::STEAM3_MAX_LEN];
+    /// let len = SteamID::from(76561197960287930).render_steam3_into(&mut buf);
+    /// assert_eq!(&buf[..len], b"[U:1:22202]");
+    /// ```
+    pub fn render_steam3_into(&self, buf: &mut [u8]) -> usize {
+        let mut writer = SliceWriter { buf, len: 0 };
+        self.write_steam3(&mut writer)
+            .expect("Buffer too short for steam3 ID");
+        writer.len
+    }
+
+    /// Writes [`SteamID::steam3_bare`] to `w`, without allocating.
+    pub fn write_steam3_bare<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
         let account_type = self.account_type();
-        let mut render_instance = false;
+        let render_instance = match account_type {
+            AccountType::Multiseat | AccountType::AnonGameServer => true,
+            AccountType::Individual => self.instance_value() != Instance::Desktop as u32,
+            _ => false,
+        };
 
-        match account_type {
-            AccountType::AnonGameServer | AccountType::Multiseat => render_instance = true,
-            AccountType::Individual => render_instance = instance != Instance::Desktop,
-            _ => (),
+        let type_char = self.type_char();
+        write!(
+            w,
+            "{}:{}:{}",
+            type_char,
+            self.universe() as u64,
+            self.account_id()
+        )?;
+        // Chats only carry the flag their type char implies, so matchmaking lobbies and other
+        // combinations spell out the whole instance
+        let implied_chat_instance = match type_char {
+            'c' => Self::CHAT_INSTANCE_FLAG_CLAN,
+            'L' => Self::CHAT_INSTANCE_FLAG_LOBBY,
+            _ => 0,
         };
+        if render_instance
+            || (account_type == AccountType::Chat && self.instance_value() != implied_chat_instance)
+        {
+            write!(w, ":{}", self.instance_value())?;
+        }
+        Ok(())
+    }
 
-        if render_instance {
-            format!(
-                "[{}:{}:{}:{}]",
-                account_type_to_char(account_type, instance),
-                self.universe() as u64,
-                self.account_id(),
-                instance as u64
-            )
-        } else {
-            format!(
-                "[{}:{}:{}]",
-                account_type_to_char(account_type, instance),
-                self.universe() as u64,
-                self.account_id()
-            )
+    // Like account_type_to_char, but checks the chat flags one by one, since matchmaking lobbies
+    // have more than one set and their instance isn't any single Instance
+    pub(crate) fn type_char(&self) -> char {
+        let account_type = self.account_type();
+        if account_type != AccountType::Chat {
+            return account_type_to_char(account_type, self.instance());
         }
+
+        let flags = self.chat_instance_flags();
+        let instance = if flags & Self::CHAT_INSTANCE_FLAG_CLAN != 0 {
+            Instance::FlagClan
+        } else if flags & Self::CHAT_INSTANCE_FLAG_LOBBY != 0 {
+            Instance::FlagLobby
+        } else {
+            Instance::All
+        };
+        account_type_to_char(account_type, instance)
     }
 
+    /// Parses a steam3 ID such as `[U:1:22202]`. Use [`suggest::steam3`] to offer a
+    /// correction for near misses like `U:1:22202`.
     pub fn from_steam3(steam3: &str) -> Result<Self, SteamIDParseError> {
-        Self::from_steam3_helper(steam3).ok_or(SteamIDParseError {})
+        Self::from_steam3_helper(steam3)
+    }
+
+    /// Parses a steam3 ID written without brackets, such as `U:1:22202` or `A:1:1234:5678`.
+    /// Unlike [`SteamID::from_steam3`], nothing may follow the ID.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from_steam3_bare("U:1:22202").unwrap();
+    /// assert_eq!(s, SteamID::from(76561197960287930));
+    /// assert_eq!(s.steam3_bare(), "U:1:22202");
+    /// ```
+    pub fn from_steam3_bare(steam3: &str) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam3(steam3.as_bytes(), false)
+    }
+
+    /// Like [`SteamID::from_steam3`], but for an ID still in a byte buffer. See
+    /// [`SteamID::from_steam2_bytes`].
+    pub fn from_steam3_bytes(steam3: &[u8]) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam3(steam3, true)
+    }
+
+    fn from_steam3_helper(steam3: &str) -> Result<Self, SteamIDParseError> {
+        Self::parse_steam3(steam3.as_bytes(), true)
     }
 
     // Parses id in the format of:
     // ^\[(type:[AGMPCgcLTIUai]):(universe:[0-4]):(account_id:[0-9]{1,10})(:(instance:[0-9]+))?\]$
-    fn from_steam3_helper(steam3: &str) -> Option<Self> {
-        let mut bytes = steam3.bytes().peekable();
-
-        if bytes.next() != Some(b'[') {
-            return None;
+    // or, if not `bracketed`, the same without the brackets and without anything following it
+    fn parse_steam3(steam3: &[u8], bracketed: bool) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::from_bytes(steam3);
+        if bracketed {
+            if cursor.peek() != Some(b'[') {
+                return Err(cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0));
+            }
+            cursor.next();
         }
 
-        let type_char = char::from(bytes.next()?);
+        let type_pos = cursor.pos;
+        let type_char = char::from(cursor.next().ok_or_else(|| cursor.unexpected())?);
         let (account_type, flag) = char_to_account_type(type_char);
         if type_char != 'i' && type_char != 'I' && account_type == AccountType::Invalid {
-            return None;
+            return Err(cursor.error_at(SteamIDParseErrorKind::InvalidAccountType, type_pos));
         }
 
-        if bytes.next() != Some(b':') {
-            return None;
-        }
+        cursor.expect(b':')?;
+        let universe_pos = cursor.pos;
+        let universe = match cursor.digit()? {
+            digit @ 0..=4 => Universe::from_u8(digit).expect("Universe is in range"),
+            _ => return Err(cursor.error_at(SteamIDParseErrorKind::InvalidUniverse, universe_pos)),
+        };
+        cursor.expect(b':')?;
 
-        let universe = bytes.next().and_then(digit_from_ascii).and_then(|digit| {
-            if digit <= 4 {
-                Universe::from_u64(u64::from(digit))
-            } else {
-                None
-            }
-        })?;
+        let (account_id, _) = cursor.number(
+            u64::from(u32::MAX),
+            SteamIDParseErrorKind::AccountIdOverflow,
+        )?;
+        let account_id = account_id as u32;
 
-        if bytes.next() != Some(b':') {
-            return None;
+        // Instance is optional. Parse it if it's there, but leave the closing ] intact
+        let raw_instance = if cursor.peek() == Some(b':') {
+            cursor.next();
+            let (instance, _) = cursor.number(0xFFFFF, SteamIDParseErrorKind::InvalidInstance)?;
+            Some(instance)
+        } else {
+            None
+        };
+
+        let instance = match (flag, type_char) {
+            (Some(flag), _) => flag,
+            (None, 'U') => Instance::Desktop,
+            (None, _) => Instance::All,
+        };
+
+        if bracketed {
+            cursor.expect(b']')?;
+        } else {
+            cursor.end()?;
         }
 
-        let mut account_id = u32::from(digit_from_ascii(bytes.next()?)?);
-        while let Some(digit) = bytes.peek().copied().and_then(digit_from_ascii) {
-            bytes.next().expect("Byte was peeked");
-            account_id = account_id.checked_mul(10)?;
-            account_id = account_id.checked_add(u32::from(digit))?;
+        let mut steamid = Self::new(account_id, instance, account_type, universe);
+        // Keep a written instance as is, even if it isn't one of Instance's values, so that
+        // every steam3 rendering parses back to the same ID
+        if let Some(raw) = raw_instance {
+            steamid.set_instance_value(raw as u32 | flag.map_or(0, |flag| flag as u32));
         }
+        Ok(steamid)
+    }
+}
 
-        // Instance is optional. Parse it if it's there, but leave the closing ] intact
-        let mut instance = {
-            let maybe_instance = if bytes.peek() == Some(&b':') {
-                bytes.next().expect("Byte was peeked");
-
-                let mut acc = u64::from(digit_from_ascii(bytes.next()?)?);
-                while let Some(digit) = bytes.peek().copied().and_then(digit_from_ascii) {
-                    bytes.next().expect("Byte was peeked");
-                    acc = acc.checked_mul(10)?;
-                    acc = acc.checked_add(u64::from(digit))?;
-                }
+/// Which convention [`SteamID::steam2_with`] follows for the universe digit.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Steam2Format {
+    /// `STEAM_0:` whatever the universe, as GoldSrc and Source games before the Orange Box print
+    /// them, and as SourceMod and SourceBans still store them.
+    GoldSrc,
+    /// The actual universe, so `STEAM_1:` for public accounts. This is what
+    /// [`SteamID::steam2`] renders.
+    OrangeBox,
+    /// Like `OrangeBox`, but pending and invalid accounts render as the placeholders SteamKit and
+    /// the Source engine print for them, `STEAM_ID_PENDING` and `UNKNOWN`.
+    /// [`SteamID::from_steam2_extended`] reads them back.
+    SteamKit,
+}
 
-                Some(Instance::from_u64(acc).unwrap_or(Instance::Invalid))
-            } else {
-                None
-            };
+/// Returned by [`SteamID::try_steam2`] for an ID with no steam2 form.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Steam2RenderError {
+    account_type: AccountType,
+}
 
-            match (maybe_instance, type_char) {
-                (None, 'U') => Instance::Desktop,
-                (None, _) | (_, 'T' | 'g') => Instance::All,
-                (Some(instance), _) => instance,
-            }
-        };
+impl Steam2RenderError {
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+}
 
-        if let Some(i) = flag {
-            instance = i;
-        }
+impl Error for Steam2RenderError {}
 
-        if bytes.next() != Some(b']') {
-            return None;
-        }
+impl Display for Steam2RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} SteamIDs have no steam2 form", self.account_type)
+    }
+}
+
+/// Returned by [`SteamID::steam2_strict`] and [`SteamID::steam3_strict`] for an ID that the
+/// format can't hold all of.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LossyRenderError {
+    steamid: SteamID,
+    format: &'static str,
+}
 
-        Some(Self::new(account_id, instance, account_type, universe))
+impl LossyRenderError {
+    pub fn steamid(&self) -> SteamID {
+        self.steamid
     }
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
-pub struct SteamIDParseError {}
+impl Error for LossyRenderError {}
+
+impl Display for LossyRenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SteamID {} can't be written as {} without losing information",
+            self.steamid.0, self.format
+        )
+    }
+}
+
+/// Why a SteamID was rejected. Use [`SteamIDParseError::kind`] to find out which one applies.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SteamIDParseErrorKind {
+    /// The input isn't in the expected format at all, e.g. a steam3 ID passed to `from_steam2`.
+    WrongFormat,
+    /// The input has the right shape, but a character where it doesn't belong.
+    UnexpectedCharacter,
+    /// The input stopped partway through an ID.
+    UnexpectedEnd,
+    InvalidUniverse,
+    InvalidAccountType,
+    InvalidInstance,
+    /// The account id doesn't fit in 32 bits.
+    AccountIdOverflow,
+    /// The input is well-formed, but its checksum is wrong, which usually means a typo.
+    ChecksumMismatch,
+    /// A steam2 ID has universe 0, which [`SteamID::from_steam2`] reads as public but
+    /// [`SteamID::from_steam2_strict`] rejects.
+    UniverseZero,
+    /// The account id is 0, but the account type needs a real one.
+    MissingAccountId,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SteamIDParseError {
+    kind: SteamIDParseErrorKind,
+    position: Option<usize>,
+    found: Option<char>,
+}
+
+impl SteamIDParseError {
+    fn new(kind: SteamIDParseErrorKind) -> Self {
+        SteamIDParseError {
+            kind,
+            position: None,
+            found: None,
+        }
+    }
+
+    pub fn kind(&self) -> SteamIDParseErrorKind {
+        self.kind
+    }
+
+    /// The byte offset into the input where the problem was found: the offending character for
+    /// syntax errors, or the start of the offending field (universe, account id, ...) otherwise.
+    /// `None` if the error didn't come from parsing a string.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// The character at [`position`](SteamIDParseError::position), or `None` if the input ended
+    /// there.
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+
+    /// A stable number identifying the [kind](SteamIDParseError::kind) of failure, for FFI,
+    /// metrics and logs. Codes are never renumbered or reused:
+    ///
+    /// | Code | Kind                  |
+    /// |------|-----------------------|
+    /// | 1    | `WrongFormat`         |
+    /// | 2    | `UnexpectedCharacter` |
+    /// | 3    | `UnexpectedEnd`       |
+    /// | 4    | `InvalidUniverse`     |
+    /// | 5    | `InvalidAccountType`  |
+    /// | 6    | `InvalidInstance`     |
+    /// | 7    | `AccountIdOverflow`   |
+    /// | 8    | `ChecksumMismatch`    |
+    /// | 9    | `UniverseZero`        |
+    /// | 10   | `MissingAccountId`    |
+    pub fn code(&self) -> u32 {
+        match self.kind {
+            SteamIDParseErrorKind::WrongFormat => 1,
+            SteamIDParseErrorKind::UnexpectedCharacter => 2,
+            SteamIDParseErrorKind::UnexpectedEnd => 3,
+            SteamIDParseErrorKind::InvalidUniverse => 4,
+            SteamIDParseErrorKind::InvalidAccountType => 5,
+            SteamIDParseErrorKind::InvalidInstance => 6,
+            SteamIDParseErrorKind::AccountIdOverflow => 7,
+            SteamIDParseErrorKind::ChecksumMismatch => 8,
+            SteamIDParseErrorKind::UniverseZero => 9,
+            SteamIDParseErrorKind::MissingAccountId => 10,
+        }
+    }
+}
 
 impl Error for SteamIDParseError {}
 
+impl Default for SteamIDParseError {
+    fn default() -> Self {
+        SteamIDParseError::new(SteamIDParseErrorKind::WrongFormat)
+    }
+}
+
 impl Display for SteamIDParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Malformed SteamID")
+        let reason = match self.kind {
+            SteamIDParseErrorKind::WrongFormat => "unrecognized format",
+            SteamIDParseErrorKind::UnexpectedCharacter => "unexpected character",
+            SteamIDParseErrorKind::UnexpectedEnd => "unexpected end of input",
+            SteamIDParseErrorKind::InvalidUniverse => "invalid universe",
+            SteamIDParseErrorKind::InvalidAccountType => "invalid account type",
+            SteamIDParseErrorKind::InvalidInstance => "invalid instance",
+            SteamIDParseErrorKind::AccountIdOverflow => "account id out of range",
+            SteamIDParseErrorKind::ChecksumMismatch => "checksum mismatch",
+            SteamIDParseErrorKind::UniverseZero => "universe 0",
+            SteamIDParseErrorKind::MissingAccountId => "missing account id",
+        };
+        write!(f, "Malformed SteamID: {}", reason)?;
+        match (self.kind, self.position, self.found) {
+            (SteamIDParseErrorKind::WrongFormat, _, _) | (_, None, _) => (),
+            (SteamIDParseErrorKind::UnexpectedCharacter, Some(position), Some(found)) => {
+                write!(f, " {:?} at byte {}", found, position)?
+            }
+            (_, Some(position), _) => write!(f, " at byte {}", position)?,
+        }
+        Ok(())
     }
 }
 
@@ -295,6 +1408,25 @@ impl From<SteamID> for u64 {
     }
 }
 
+/// For steam64s stored as signed integers, as many databases do. Validates like
+/// [`SteamID::from_steam64`]; negative values would have a universe of 128 or more, so they're
+/// rejected with [`SteamIDParseErrorKind::InvalidUniverse`].
+impl TryFrom<i64> for SteamID {
+    type Error = SteamIDParseError;
+
+    fn try_from(s: i64) -> Result<Self, Self::Error> {
+        SteamID::from_steam64(s as u64)
+    }
+}
+
+/// Reinterprets the steam64 as signed. This is lossless, and positive for every ID with a valid
+/// universe; only unvalidated `From<u64>` IDs can come out negative.
+impl From<SteamID> for i64 {
+    fn from(s: SteamID) -> Self {
+        s.0 as i64
+    }
+}
+
 impl From<SteamID> for String {
     /// Returns a Steam3 representation of the SteamID
     fn from(s: SteamID) -> Self {
@@ -307,12 +1439,24 @@ impl From<SteamID> for String {
 impl FromStr for SteamID {
     type Err = SteamIDParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.parse::<u64>() {
-            Ok(parsed) => Ok(parsed.into()),
-            Result::Err(_) => match Self::from_steam2(s) {
-                Ok(parsed) => Ok(parsed),
-                Result::Err(_) => Self::from_steam3(s),
-            },
+        SteamID::parse_any(s)
+    }
+}
+
+impl SteamID {
+    // The body of FromStr, shared with parse_many
+    fn parse_any(s: &str) -> Result<Self, SteamIDParseError> {
+        if let Ok(parsed) = s.parse::<u64>() {
+            return Ok(parsed.into());
+        }
+
+        // Report the error from whichever format the input looks like it was meant to be
+        let steam2 = Self::from_steam2_helper(s);
+        let steam3 = Self::from_steam3_helper(s);
+        match (steam2, steam3) {
+            (Ok(parsed), _) | (_, Ok(parsed)) => Ok(parsed),
+            (Err(e), _) if e.kind != SteamIDParseErrorKind::WrongFormat => Err(e),
+            (_, Err(e)) => Err(e),
         }
     }
 }
@@ -363,8 +1507,56 @@ impl Debug for SteamID {
     }
 }
 
+/// Renders the steam64 by default. The alternate flag selects steam3, and so does a precision of
+/// 3; a precision of 2 selects steam2. Width, fill and alignment apply as usual.
+///
+/// ```
+/// # use steamid_ng::SteamID;
+/// let s = SteamID::from(76561197960287930);
+/// assert_eq!(format!("{}", s), "76561197960287930");
+/// assert_eq!(format!("{:#}", s), "[U:1:22202]");
+/// assert_eq!(format!("{:.2}", s), "STEAM_1:0:11101");
+/// assert_eq!(format!("{:>13.3}", s), "  [U:1:22202]");
+/// ```
+impl Display for SteamID {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        // Fits all of the formats, so padding doesn't need an allocation
+        let mut buf = [0; SteamID::STEAM3_MAX_LEN];
+        let mut writer = SliceWriter {
+            buf: &mut buf,
+            len: 0,
+        };
+        match f.precision() {
+            Some(2) => self.write_steam2(&mut writer)?,
+            Some(3) => self.write_steam3(&mut writer)?,
+            _ if f.alternate() => self.write_steam3(&mut writer)?,
+            _ => write!(writer, "{}", self.0)?,
+        }
+        let len = writer.len;
+        let rendered = std::str::from_utf8(&buf[..len]).expect("IDs are ASCII");
+
+        // Formatter::pad would truncate to the precision, which picks the format here instead
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+        let (before, after) = match f.align() {
+            None | Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+        for _ in 0..before {
+            f.write_char(f.fill())?;
+        }
+        f.write_str(rendered)?;
+        for _ in 0..after {
+            f.write_char(f.fill())?;
+        }
+        Ok(())
+    }
+}
+
 enum_from_primitive!(
-    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
     pub enum AccountType {
         Invalid = 0,
         Individual = 1,
@@ -375,11 +1567,36 @@ enum_from_primitive!(
         ContentServer = 6,
         Clan = 7,
         Chat = 8,
-        P2PSuperSeeder = 9,
+        ConsoleUser = 9,
         AnonUser = 10,
     }
 );
 
+impl AccountType {
+    /// The old name of [`AccountType::ConsoleUser`], from before Valve repurposed the type.
+    #[deprecated(note = "Renamed to ConsoleUser")]
+    #[allow(non_upper_case_globals)]
+    pub const P2PSuperSeeder: AccountType = AccountType::ConsoleUser;
+}
+
+impl Display for AccountType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            AccountType::Invalid => "Invalid",
+            AccountType::Individual => "Individual",
+            AccountType::Multiseat => "Multiseat",
+            AccountType::GameServer => "GameServer",
+            AccountType::AnonGameServer => "AnonGameServer",
+            AccountType::Pending => "Pending",
+            AccountType::ContentServer => "ContentServer",
+            AccountType::Clan => "Clan",
+            AccountType::Chat => "Chat",
+            AccountType::ConsoleUser => "ConsoleUser",
+            AccountType::AnonUser => "AnonUser",
+        })
+    }
+}
+
 pub fn account_type_to_char(account_type: AccountType, instance: Instance) -> char {
     match account_type {
         AccountType::Invalid => 'I',
@@ -400,7 +1617,7 @@ pub fn account_type_to_char(account_type: AccountType, instance: Instance) -> ch
             }
         }
         AccountType::AnonUser => 'a',
-        AccountType::P2PSuperSeeder => 'i', // Invalid (?)
+        AccountType::ConsoleUser => 'i', // Invalid (?)
     }
 }
 
@@ -422,12 +1639,12 @@ pub fn char_to_account_type(c: char) -> (AccountType, Option<Instance>) {
 
         'a' => (AccountType::AnonUser, None),
 
-        'I' | 'i' | _ => (AccountType::Invalid, None),
+        _ => (AccountType::Invalid, None),
     }
 }
 
 enum_from_primitive!(
-    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
     pub enum Universe {
         Invalid = 0,
         Public = 1,
@@ -437,8 +1654,20 @@ enum_from_primitive!(
     }
 );
 
+impl Display for Universe {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Universe::Invalid => "Invalid",
+            Universe::Public => "Public",
+            Universe::Beta => "Beta",
+            Universe::Internal => "Internal",
+            Universe::Dev => "Dev",
+        })
+    }
+}
+
 enum_from_primitive!(
-    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
     pub enum Instance {
         All = 0,
         Desktop = 1,
@@ -452,3 +1681,60 @@ enum_from_primitive!(
         FlagMMSLobby = 0x100000 >> 3,
     }
 );
+
+impl Instance {
+    /// The instance with the raw value `raw`, or [`Instance::Invalid`] if it has no variant, as
+    /// [`SteamID::instance`] reads it. Use [`SteamID::instance_value`] and
+    /// [`SteamID::set_instance_value`] to keep other values intact.
+    pub fn from_raw(raw: u32) -> Self {
+        Instance::from_u32(raw).unwrap_or(Instance::Invalid)
+    }
+
+    pub fn raw(self) -> u32 {
+        self as u32
+    }
+
+    /// This instance as a set of flags. [`Instance::All`] and [`Instance::Invalid`] have none.
+    pub fn flags(self) -> InstanceFlags {
+        match self {
+            Instance::Invalid => InstanceFlags::empty(),
+            _ => InstanceFlags::from_bits_truncate(self as u32),
+        }
+    }
+}
+
+impl Display for Instance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Instance::All => "All",
+            Instance::Desktop => "Desktop",
+            Instance::Console => "Console",
+            Instance::Web => "Web",
+            Instance::Invalid => "Invalid",
+            Instance::FlagClan => "FlagClan",
+            Instance::FlagLobby => "FlagLobby",
+            Instance::FlagMMSLobby => "FlagMMSLobby",
+        })
+    }
+}
+
+// Formats the raw bits, so `{:#x}` and `{:#b}` work when inspecting packets
+macro_rules! radix_fmt {
+    ($($fmt:ident),*) => {
+        $(
+            impl fmt::$fmt for SteamID {
+                fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                    fmt::$fmt::fmt(&self.0, f)
+                }
+            }
+
+            impl fmt::$fmt for Instance {
+                fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                    fmt::$fmt::fmt(&(*self as u32), f)
+                }
+            }
+        )*
+    };
+}
+
+radix_fmt!(LowerHex, UpperHex, Binary);
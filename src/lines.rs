@@ -0,0 +1,71 @@
+use std::io::{self, BufRead};
+
+use crate::{SteamID, SteamIDParseError};
+
+/// Reads one ID per line from `reader`, parsing each the way `FromStr` does, without holding more
+/// than a line in memory. Each item is the (1-based) line number with either the ID or the
+/// reason the line didn't parse. Whitespace around IDs is ignored, and blank lines are skipped.
+///
+/// Lines that aren't UTF-8 are decoded lossily, so they come back as parse errors and reading
+/// carries on past them. Reading stops after the first I/O error.
+///
+/// ```
+/// # use steamid_ng::{SteamID, SteamIDLines};
+/// let bans = "STEAM_1:0:11101\n\n[U:1:22203]\nnonsense\n";
+/// let parsed: Vec<_> = SteamIDLines::new(bans.as_bytes())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(parsed[0], (1, Ok(SteamID::from(76561197960287930))));
+/// assert_eq!(parsed[1], (3, Ok(SteamID::from(76561197960287931))));
+/// assert_eq!(parsed[2].0, 4);
+/// assert!(parsed[2].1.is_err());
+/// ```
+#[derive(Debug)]
+pub struct SteamIDLines<R> {
+    reader: R,
+    line: usize,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl<R: BufRead> SteamIDLines<R> {
+    pub fn new(reader: R) -> Self {
+        SteamIDLines {
+            reader,
+            line: 0,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for SteamIDLines<R> {
+    type Item = io::Result<(usize, Result<SteamID, SteamIDParseError>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    let line = String::from_utf8_lossy(&self.buf);
+                    let line = line.trim();
+                    if !line.is_empty() {
+                        return Some(Ok((self.line, line.parse())));
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        None
+    }
+}
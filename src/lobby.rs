@@ -0,0 +1,37 @@
+use crate::{AccountType, SteamID, SteamIDParseError};
+
+impl SteamID {
+    /// Extracts a lobby SteamID from a rich-presence connect string such as
+    /// `+connect_lobby 109775241017770404`, or from a `steam://joinlobby/<app>/<lobby>/...` URL.
+    ///
+    /// The ID must be a chat account with the lobby instance flag set.
+    pub fn from_lobby_connect(connect: &str) -> Result<Self, SteamIDParseError> {
+        Self::from_lobby_connect_helper(connect).ok_or(SteamIDParseError::default())
+    }
+
+    fn from_lobby_connect_helper(connect: &str) -> Option<Self> {
+        let connect = connect.trim();
+        let lobby = if let Some(rest) = connect.strip_prefix("steam://joinlobby/") {
+            // steam://joinlobby/<app id>/<lobby id>[/<owner id>]
+            let mut parts = rest.split('/');
+            parts.next()?.parse::<u32>().ok()?;
+            parts.next()?
+        } else {
+            let mut args = connect.split_whitespace();
+            args.find(|&arg| arg == "+connect_lobby")?;
+            args.next()?
+        };
+
+        if !lobby.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let id = SteamID::from(lobby.parse::<u64>().ok()?);
+        if id.account_type() != AccountType::Chat
+            || id.chat_instance_flags() & SteamID::CHAT_INSTANCE_FLAG_LOBBY == 0
+        {
+            return None;
+        }
+
+        Some(id)
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{char_to_account_type, AccountType, Instance, Universe};
+
+/// A name that doesn't stand for any value of the enum it was parsed as.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ParseNameError {
+    what: &'static str,
+}
+
+impl Error for ParseNameError {}
+
+impl Display for ParseNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown {}", self.what)
+    }
+}
+
+const ACCOUNT_TYPES: &[AccountType] = &[
+    AccountType::Invalid,
+    AccountType::Individual,
+    AccountType::Multiseat,
+    AccountType::GameServer,
+    AccountType::AnonGameServer,
+    AccountType::Pending,
+    AccountType::ContentServer,
+    AccountType::Clan,
+    AccountType::Chat,
+    AccountType::ConsoleUser,
+    AccountType::AnonUser,
+];
+
+/// Accepts the names `Display` gives, in any case, and the steam3 type characters, which are
+/// case-sensitive since `c` and `C` are different types.
+///
+/// ```
+/// # use steamid_ng::AccountType;
+/// assert_eq!("clan".parse(), Ok(AccountType::Clan));
+/// assert_eq!("g".parse(), Ok(AccountType::Clan));
+/// assert_eq!("L".parse(), Ok(AccountType::Chat));
+/// assert!("G ".parse::<AccountType>().is_err());
+/// ```
+impl FromStr for AccountType {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return match (c, char_to_account_type(c).0) {
+                ('I', _) => Ok(AccountType::Invalid),
+                ('i', _) => Ok(AccountType::ConsoleUser),
+                (_, AccountType::Invalid) => Err(ParseNameError {
+                    what: "account type",
+                }),
+                (_, account_type) => Ok(account_type),
+            };
+        }
+
+        find_name(ACCOUNT_TYPES, s).ok_or(ParseNameError {
+            what: "account type",
+        })
+    }
+}
+
+const UNIVERSES: &[Universe] = &[
+    Universe::Invalid,
+    Universe::Public,
+    Universe::Beta,
+    Universe::Internal,
+    Universe::Dev,
+];
+
+/// Accepts the names `Display` gives, in any case.
+///
+/// ```
+/// # use steamid_ng::Universe;
+/// assert_eq!("public".parse(), Ok(Universe::Public));
+/// assert_eq!(Universe::Beta.to_string().parse(), Ok(Universe::Beta));
+/// ```
+impl FromStr for Universe {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        find_name(UNIVERSES, s).ok_or(ParseNameError { what: "universe" })
+    }
+}
+
+const INSTANCES: &[Instance] = &[
+    Instance::All,
+    Instance::Desktop,
+    Instance::Console,
+    Instance::Web,
+    Instance::Invalid,
+    Instance::FlagClan,
+    Instance::FlagLobby,
+    Instance::FlagMMSLobby,
+];
+
+/// Accepts the names `Display` gives, in any case, and the raw values of the variants in
+/// decimal. This crate has a single enum for instance types and chat flags, so both are parsed
+/// here.
+///
+/// ```
+/// # use steamid_ng::Instance;
+/// assert_eq!("web".parse(), Ok(Instance::Web));
+/// assert_eq!("FlagLobby".parse(), Ok(Instance::FlagLobby));
+/// assert_eq!("2".parse(), Ok(Instance::Console));
+/// assert!("3".parse::<Instance>().is_err());
+/// ```
+impl FromStr for Instance {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let by_value = s
+            .parse::<u32>()
+            .ok()
+            .and_then(|raw| INSTANCES.iter().copied().find(|i| i.raw() == raw));
+        by_value
+            .or_else(|| find_name(INSTANCES, s))
+            .ok_or(ParseNameError { what: "instance" })
+    }
+}
+
+fn find_name<T: Copy + Display>(values: &[T], name: &str) -> Option<T> {
+    values
+        .iter()
+        .copied()
+        .find(|value| value.to_string().eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,208 @@
+//! Configurable leniency for parsing messy input, see [`SteamID::parse_with`].
+
+use std::borrow::Cow;
+
+use crate::{
+    char_to_account_type, AccountType, Cursor, SteamID, SteamIDParseError, SteamIDParseErrorKind,
+    ValidationLevel,
+};
+
+/// How forgiving [`SteamID::parse_with`] is. The default is exactly as strict as `FromStr`: every
+/// format is allowed, but nothing is normalized. Options are set by chaining:
+///
+/// ```
+/// # use steamid_ng::{ParseOptions, SteamID};
+/// let gabe = SteamID::from(76561197960287930);
+/// let options = ParseOptions::lenient().allow_steam64(false);
+/// assert_eq!(SteamID::parse_with(" steam_1:0:11101\n", options), Ok(gabe));
+/// assert_eq!(SteamID::parse_with("u:1:22202", options), Ok(gabe));
+/// assert!(SteamID::parse_with("76561197960287930", options).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ParseOptions {
+    trim: bool,
+    case_insensitive: bool,
+    optional_brackets: bool,
+    reject_universe_zero: bool,
+    steam64: bool,
+    steam2: bool,
+    steam3: bool,
+    validation: Option<ValidationLevel>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            trim: false,
+            case_insensitive: false,
+            optional_brackets: false,
+            reject_universe_zero: false,
+            steam64: true,
+            steam2: true,
+            steam3: true,
+            validation: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every kind of leniency, with every format allowed.
+    pub fn lenient() -> Self {
+        Self::default()
+            .trim(true)
+            .case_insensitive(true)
+            .optional_brackets(true)
+    }
+
+    /// Ignores whitespace around the ID.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Accepts the `STEAM_` prefix in any case, and lowercase steam3 type letters that don't
+    /// already mean something else: `u` is read as `U`, but `c` and `C` stay different types.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Accepts steam3 IDs without their brackets, like [`SteamID::from_steam3_bare`].
+    pub fn optional_brackets(mut self, optional_brackets: bool) -> Self {
+        self.optional_brackets = optional_brackets;
+        self
+    }
+
+    /// Rejects steam2 IDs with universe 0, like [`SteamID::from_steam2_strict`]. This is the
+    /// opposite of leniency, so [`ParseOptions::lenient`] leaves it off.
+    pub fn reject_universe_zero(mut self, reject: bool) -> Self {
+        self.reject_universe_zero = reject;
+        self
+    }
+
+    /// Checks parsed IDs against `level` as well. By default steam2 and steam3 IDs only have to
+    /// be well-formed, and any steam64 is accepted.
+    pub fn validation(mut self, level: ValidationLevel) -> Self {
+        self.validation = Some(level);
+        self
+    }
+
+    pub fn allow_steam64(mut self, allow: bool) -> Self {
+        self.steam64 = allow;
+        self
+    }
+
+    pub fn allow_steam2(mut self, allow: bool) -> Self {
+        self.steam2 = allow;
+        self
+    }
+
+    pub fn allow_steam3(mut self, allow: bool) -> Self {
+        self.steam3 = allow;
+        self
+    }
+}
+
+impl SteamID {
+    /// Parses whichever of the formats allowed by `options` the input is in, the way `FromStr`
+    /// does. Error positions refer to `s` as passed in, before any trimming.
+    pub fn parse_with(s: &str, options: ParseOptions) -> Result<Self, SteamIDParseError> {
+        let parsed = Self::parse_with_helper(s, options)?;
+        if let Some(level) = options.validation {
+            parsed.validate(level)?;
+        }
+        Ok(parsed)
+    }
+
+    fn parse_with_helper(s: &str, options: ParseOptions) -> Result<Self, SteamIDParseError> {
+        let (input, offset) = if options.trim {
+            let start = s.trim_start();
+            (start.trim_end(), s.len() - start.len())
+        } else {
+            (s, 0)
+        };
+
+        if options.steam64 {
+            if let Ok(parsed) = input.parse::<u64>() {
+                return Ok(parsed.into());
+            }
+        }
+
+        let steam2 = if options.steam2 {
+            Some(SteamID::parse_steam2(
+                normalize_steam2(input, options).as_bytes(),
+                options.reject_universe_zero,
+            ))
+        } else {
+            None
+        };
+        let steam3 = if options.steam3 {
+            Some(parse_steam3(input, options))
+        } else {
+            None
+        };
+
+        // Report the error from whichever format the input looks like it was meant to be
+        let error = match (steam2, steam3) {
+            (Some(Ok(parsed)), _) | (_, Some(Ok(parsed))) => return Ok(parsed),
+            (Some(Err(e)), _) if e.kind != SteamIDParseErrorKind::WrongFormat => e,
+            (_, Some(Err(e))) | (Some(Err(e)), None) => e,
+            (None, None) if options.steam64 => steam64_error(input),
+            (None, None) => Cursor::new(input).error_at(SteamIDParseErrorKind::WrongFormat, 0),
+        };
+
+        // Point back into the untrimmed, unnormalized input
+        let position = error.position.map(|p| p + offset);
+        Err(SteamIDParseError {
+            position,
+            found: position.and_then(|p| s.get(p..)?.chars().next()),
+            ..error
+        })
+    }
+}
+
+fn normalize_steam2(input: &str, options: ParseOptions) -> Cow<'_, str> {
+    match input.get(..6) {
+        Some(prefix) if options.case_insensitive && prefix.eq_ignore_ascii_case("STEAM_") => {
+            Cow::Owned(format!("STEAM_{}", &input[6..]))
+        }
+        _ => Cow::Borrowed(input),
+    }
+}
+
+fn parse_steam3(input: &str, options: ParseOptions) -> Result<SteamID, SteamIDParseError> {
+    let bracketed = input.starts_with('[');
+    // Without brackets, only something starting like `U:` is meant to be a steam3 ID
+    if !bracketed && (!options.optional_brackets || input.as_bytes().get(1) != Some(&b':')) {
+        return Err(Cursor::new(input).error_at(SteamIDParseErrorKind::WrongFormat, 0));
+    }
+
+    let type_pos = if bracketed { 1 } else { 0 };
+    let input = match input.as_bytes().get(type_pos) {
+        Some(&c)
+            if options.case_insensitive
+                && c.is_ascii_lowercase()
+                && char_to_account_type(char::from(c)).0 == AccountType::Invalid =>
+        {
+            let mut owned = input.to_owned();
+            owned[type_pos..=type_pos].make_ascii_uppercase();
+            Cow::Owned(owned)
+        }
+        _ => Cow::Borrowed(input),
+    };
+
+    SteamID::parse_steam3(input.as_bytes(), bracketed)
+}
+
+fn steam64_error(input: &str) -> SteamIDParseError {
+    let mut cursor = Cursor::new(input);
+    // A run of digits too long for a steam64 isn't one
+    match cursor.number(u64::MAX, SteamIDParseErrorKind::WrongFormat) {
+        Ok(_) => cursor.end().expect_err("Input doesn't parse as a u64"),
+        Err(e) => e,
+    }
+}
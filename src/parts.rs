@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountType, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe};
+
+/// The fields of a [`SteamID`] as a plain struct, for matching on and persisting them.
+///
+/// The instance is kept raw, as [`SteamID::instance_value`] gives it, so the numbered instances
+/// of anonymous game servers and the combined flags of matchmaking lobbies survive. Account types
+/// and universes [`AccountType`] and [`Universe`] have no variant for come out as `Invalid`.
+/// Converting back fails with [`SteamIDParseErrorKind::InvalidInstance`] if `instance` doesn't
+/// fit in the 20-bit instance field.
+///
+/// ```
+/// # use steamid_ng::{AccountType, SteamID, SteamIDParts, Universe};
+/// let parts = SteamIDParts::from(SteamID::from(76561197960287930));
+/// assert_eq!(
+///     parts,
+///     SteamIDParts {
+///         account_id: 22202,
+///         instance: 1,
+///         account_type: AccountType::Individual,
+///         universe: Universe::Public,
+///     }
+/// );
+/// assert_eq!(SteamID::try_from(parts), Ok(SteamID::from(76561197960287930)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SteamIDParts {
+    pub account_id: u32,
+    pub instance: u32,
+    pub account_type: AccountType,
+    pub universe: Universe,
+}
+
+impl From<SteamID> for SteamIDParts {
+    fn from(steamid: SteamID) -> Self {
+        SteamIDParts {
+            account_id: steamid.account_id(),
+            instance: steamid.instance_value(),
+            account_type: steamid.account_type(),
+            universe: steamid.universe(),
+        }
+    }
+}
+
+impl TryFrom<SteamIDParts> for SteamID {
+    type Error = SteamIDParseError;
+
+    fn try_from(parts: SteamIDParts) -> Result<Self, Self::Error> {
+        if parts.instance > SteamID::ACCOUNT_INSTANCE_MASK {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidInstance,
+            ));
+        }
+
+        let mut steamid = SteamID::new(
+            parts.account_id,
+            Instance::All,
+            parts.account_type,
+            parts.universe,
+        );
+        steamid.set_instance_value(parts.instance);
+        Ok(steamid)
+    }
+}
@@ -0,0 +1,81 @@
+//! Kani harnesses proving the parsers and accessors can't panic. Run them with `cargo kani`.
+//!
+//! Parser inputs are bounded to a handful of bytes, which is enough to reach every branch
+//! (including the overflow checks, via over-long digit runs) without the state space exploding.
+
+use std::str::FromStr;
+
+use crate::{char_to_account_type, AccountType, Instance, SteamID, Universe};
+
+const MAX_INPUT_LEN: usize = 24;
+
+fn any_str(bytes: &[u8; MAX_INPUT_LEN]) -> Option<&str> {
+    let len: usize = kani::any();
+    kani::assume(len <= MAX_INPUT_LEN);
+    std::str::from_utf8(&bytes[..len]).ok()
+}
+
+#[kani::proof]
+fn accessors_never_panic() {
+    let mut steamid = SteamID::from(kani::any::<u64>());
+    let _ = steamid.account_id();
+    let _ = steamid.account_id_non_zero();
+    let _ = steamid.instance();
+    let _ = steamid.account_type();
+    let _ = steamid.universe();
+
+    steamid.set_account_id(kani::any());
+    steamid.set_instance(Instance::Web);
+    steamid.set_account_type(AccountType::AnonUser);
+    steamid.set_universe(Universe::Dev);
+}
+
+#[kani::proof]
+fn setters_only_touch_their_field() {
+    let raw: u64 = kani::any();
+    let mut steamid = SteamID::from(raw);
+    steamid.set_account_id(kani::any());
+
+    assert_eq!(u64::from(steamid) >> 32, raw >> 32);
+}
+
+#[kani::proof]
+fn char_to_account_type_never_panics() {
+    let _ = char_to_account_type(kani::any());
+}
+
+#[kani::proof]
+#[kani::unwind(26)]
+fn from_steam2_never_panics() {
+    let bytes: [u8; MAX_INPUT_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let _ = SteamID::from_steam2(s);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(26)]
+fn from_steam3_never_panics() {
+    let bytes: [u8; MAX_INPUT_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let _ = SteamID::from_steam3(s);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(26)]
+fn from_str_never_panics() {
+    let bytes: [u8; MAX_INPUT_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let _ = SteamID::from_str(s);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(26)]
+fn from_lobby_connect_never_panics() {
+    let bytes: [u8; MAX_INPUT_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let _ = SteamID::from_lobby_connect(s);
+    }
+}
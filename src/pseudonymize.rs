@@ -0,0 +1,125 @@
+//! Keyed pseudonyms for SteamIDs, for analytics that need to tell users apart without storing who
+//! they are.
+//!
+//! A [`Pseudonymizer`] maps each SteamID to the same [`Pseudonym`] for as long as its key stays the
+//! same, using keyed BLAKE3. Without the key, pseudonyms can't be linked back to SteamIDs, or even
+//! checked against a guessed one; keep it as secret as the data it protects, and rotate it to
+//! unlink old pseudonyms from new ones.
+//!
+//! ```
+//! # use steamid_ng::{pseudonymize::Pseudonymizer, SteamID};
+//! let pseudonymizer = Pseudonymizer::new([7; 32]);
+//! let a = pseudonymizer.pseudonymize(SteamID::from(76561197960287930));
+//! let b = pseudonymizer.pseudonymize(SteamID::from(76561197960287930));
+//! assert_eq!(a, b);
+//! assert_eq!(a.to_string().len(), 64);
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use crate::SteamID;
+
+/// Derives [`Pseudonym`]s from SteamIDs with a secret key.
+#[derive(Clone)]
+pub struct Pseudonymizer {
+    key: [u8; 32],
+}
+
+impl Pseudonymizer {
+    pub fn new(key: [u8; 32]) -> Self {
+        Pseudonymizer { key }
+    }
+
+    /// Derives the key from a secret of any length, such as a passphrase from configuration.
+    /// `context` should be unique to the application and purpose, so the same secret used
+    /// elsewhere gives unrelated pseudonyms.
+    pub fn derive(context: &str, secret: &[u8]) -> Self {
+        Pseudonymizer {
+            key: blake3::derive_key(context, secret),
+        }
+    }
+
+    pub fn pseudonymize(&self, steamid: SteamID) -> Pseudonym {
+        let hash = blake3::keyed_hash(&self.key, &u64::from(steamid).to_le_bytes());
+        Pseudonym(*hash.as_bytes())
+    }
+}
+
+// The key mustn't end up in logs
+impl Debug for Pseudonymizer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pseudonymizer").finish_non_exhaustive()
+    }
+}
+
+/// An opaque stand-in for a SteamID. Displays as 64 lowercase hex digits.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pseudonym([u8; 32]);
+
+impl Pseudonym {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Pseudonym {
+    fn from(bytes: [u8; 32]) -> Self {
+        Pseudonym(bytes)
+    }
+}
+
+impl Display for Pseudonym {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for b in &self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Remembers the SteamID behind every pseudonym it hands out, for the few places (support, abuse
+/// handling) that must be able to go back. The table is as sensitive as the SteamIDs themselves.
+///
+/// ```
+/// # use steamid_ng::{pseudonymize::{PseudonymTable, Pseudonymizer}, SteamID};
+/// let mut table = PseudonymTable::new(Pseudonymizer::new([7; 32]));
+/// let pseudonym = table.pseudonymize(SteamID::from(76561197960287930));
+/// assert_eq!(table.lookup(&pseudonym), Some(SteamID::from(76561197960287930)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PseudonymTable {
+    pseudonymizer: Pseudonymizer,
+    steamids: HashMap<Pseudonym, SteamID>,
+}
+
+impl PseudonymTable {
+    pub fn new(pseudonymizer: Pseudonymizer) -> Self {
+        PseudonymTable {
+            pseudonymizer,
+            steamids: HashMap::new(),
+        }
+    }
+
+    /// Like [`Pseudonymizer::pseudonymize`], remembering the SteamID.
+    pub fn pseudonymize(&mut self, steamid: SteamID) -> Pseudonym {
+        let pseudonym = self.pseudonymizer.pseudonymize(steamid);
+        self.steamids.insert(pseudonym, steamid);
+        pseudonym
+    }
+
+    /// The SteamID behind `pseudonym`, if this table handed it out.
+    pub fn lookup(&self, pseudonym: &Pseudonym) -> Option<SteamID> {
+        self.steamids.get(pseudonym).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steamids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steamids.is_empty()
+    }
+}
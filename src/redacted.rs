@@ -0,0 +1,53 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::SteamID;
+
+// Keeps the `7656119796028` of `76561197960287930`, leaving the last four digits masked
+const DEFAULT_KEEP: usize = 13;
+
+/// A steam64 with all but its leading digits masked, for logs that shouldn't hold complete
+/// identifiers. Created by [`SteamID::redacted`].
+///
+/// Every individual in the public universe shares the leading `7656119`, so the masked trailing
+/// digits are the ones that tell accounts apart. Keeping fewer digits hides more.
+///
+/// ```
+/// # use steamid_ng::SteamID;
+/// let s = SteamID::from(76561197960287930);
+/// assert_eq!(s.redacted().to_string(), "7656119796028****");
+/// assert_eq!(s.redacted().keep(7).to_string(), "7656119**********");
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Redacted {
+    steamid: SteamID,
+    keep: usize,
+}
+
+impl Redacted {
+    /// How many leading digits to leave readable, 13 by default.
+    pub fn keep(mut self, digits: usize) -> Self {
+        self.keep = digits;
+        self
+    }
+}
+
+impl Display for Redacted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let steam64 = u64::from(self.steamid).to_string();
+        let keep = self.keep.min(steam64.len());
+        let mut redacted = String::with_capacity(steam64.len());
+        redacted.push_str(&steam64[..keep]);
+        redacted.extend(std::iter::repeat('*').take(steam64.len() - keep));
+        f.pad(&redacted)
+    }
+}
+
+impl SteamID {
+    /// Displays the steam64 with its trailing digits masked, see [`Redacted`].
+    pub fn redacted(&self) -> Redacted {
+        Redacted {
+            steamid: *self,
+            keep: DEFAULT_KEEP,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Helpers for use with serde's `#[serde(with = "...")]` attribute.
+
+/// (De)serializes an `Option<SteamID>` where an empty string means `None`, as returned by several
+/// Web API endpoints.
+///
+/// `null` is also accepted as `None`. Add `#[serde(default)]` as well if the field may be missing
+/// entirely. `None` is serialized back as an empty string.
+///
+/// ```
+/// # use steamid_ng::SteamID;
+/// #[derive(serde::Deserialize)]
+/// struct Summary {
+///     #[serde(with = "steamid_ng::serde_helpers::empty_string_as_none")]
+///     primaryclanid: Option<SteamID>,
+/// }
+///
+/// let s: Summary = serde_json::from_str(r#"{"primaryclanid": ""}"#).unwrap();
+/// assert_eq!(s.primaryclanid, None);
+/// ```
+pub mod empty_string_as_none {
+    use std::{
+        fmt::{self, Formatter},
+        str::FromStr,
+    };
+
+    use serde::{
+        de::{self, Visitor},
+        Deserializer, Serialize, Serializer,
+    };
+
+    use crate::SteamID;
+
+    pub fn serialize<S>(value: &Option<SteamID>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(steamid) => steamid.serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<SteamID>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(OptionalSteamIDVisitor)
+    }
+
+    struct OptionalSteamIDVisitor;
+    impl<'de> Visitor<'de> for OptionalSteamIDVisitor {
+        type Value = Option<SteamID>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a SteamID or an empty string")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Option<SteamID>, E>
+        where
+            E: de::Error,
+        {
+            if value.is_empty() {
+                return Ok(None);
+            }
+
+            SteamID::from_str(value)
+                .map(Some)
+                .map_err(|_| E::custom(format!("Invalid SteamID: {}", value)))
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Option<SteamID>, E>
+        where
+            E: de::Error,
+        {
+            Ok(Some(value.into()))
+        }
+
+        fn visit_unit<E>(self) -> Result<Option<SteamID>, E>
+        where
+            E: de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_none<E>(self) -> Result<Option<SteamID>, E>
+        where
+            E: de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Option<SteamID>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
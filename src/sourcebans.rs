@@ -0,0 +1,257 @@
+//! Reading and writing ban lists in the shape SourceBans-style panels export them.
+//!
+//! Both the CSV and JSON exports are rows from the `sb_bans` table, of which only `authid` (a
+//! steam2 ID), `reason` and `length` (in seconds, 0 for permanent) are used here. Any other
+//! columns are ignored when reading. The JSON form goes through serde, so parse it with e.g.
+//! `serde_json::from_str::<Vec<SourceBansBan>>`.
+//!
+//! SourceBans always stores steam2 IDs with universe 0, so that's what gets written back out.
+//! The universe is lost on the way, and IDs read back are public ones. Only individuals have a
+//! steam2 form, so writing a ban for any other account type fails.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountType, Steam2Format, Steam2RenderError, SteamID};
+
+/// One row of an export.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceBansBan {
+    #[serde(rename = "authid", with = "authid")]
+    pub steamid: SteamID,
+    pub reason: String,
+    /// `None` for permanent bans.
+    #[serde(rename = "length", with = "length")]
+    pub duration: Option<Duration>,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SourceBansError {
+    line: usize,
+}
+
+impl SourceBansError {
+    /// The (1-based) line of the input that the offending record starts on.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Error for SourceBansError {}
+
+impl Display for SourceBansError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Malformed SourceBans export on line {}", self.line)
+    }
+}
+
+/// Parses a CSV export. The first row must be a header naming at least the `authid`, `reason`
+/// and `length` columns, in any order.
+pub fn parse_csv(input: &str) -> Result<Vec<SourceBansBan>, SourceBansError> {
+    let mut records = csv_records(input)?.into_iter();
+    let (header_line, header) = records.next().ok_or(SourceBansError { line: 1 })?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+            .ok_or(SourceBansError { line: header_line })
+    };
+    let (authid, reason, length) = (column("authid")?, column("reason")?, column("length")?);
+
+    records
+        .map(|(line, fields)| {
+            let field = |i: usize| fields.get(i).ok_or(SourceBansError { line });
+            let steamid = SteamID::from_steam2(field(authid)?.trim())
+                .map_err(|_| SourceBansError { line })?;
+            let seconds: u64 = field(length)?
+                .trim()
+                .parse()
+                .map_err(|_| SourceBansError { line })?;
+
+            Ok(SourceBansBan {
+                steamid,
+                reason: field(reason)?.clone(),
+                duration: length::from_seconds(seconds),
+            })
+        })
+        .collect()
+}
+
+/// Writes `bans` as a CSV export with an `authid,reason,length` header. Fails if any of them
+/// isn't for an individual.
+pub fn to_csv(bans: &[SourceBansBan]) -> Result<String, Steam2RenderError> {
+    let mut csv = String::from("authid,reason,length\n");
+    for ban in bans {
+        csv.push_str(&authid::try_render(ban.steamid)?);
+        csv.push(',');
+        if ban.reason.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&ban.reason.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(&ban.reason);
+        }
+        csv.push(',');
+        csv.push_str(&length::to_seconds(ban.duration).to_string());
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+// Splits RFC 4180 CSV into records, each tagged with the line it starts on. Quoted fields may
+// contain commas, newlines and doubled quotes. Blank lines are skipped.
+fn csv_records(input: &str) -> Result<Vec<(usize, Vec<String>)>, SourceBansError> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                if !fields.is_empty() || !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                line += 1;
+                record_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(SourceBansError { line: record_line });
+    }
+    if !fields.is_empty() || !field.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+
+    Ok(records)
+}
+
+pub(crate) mod authid {
+    use serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+    use super::*;
+
+    // Like SteamID::try_steam2, but in the GoldSrc format
+    pub(crate) fn try_render(steamid: SteamID) -> Result<String, Steam2RenderError> {
+        match steamid.account_type() {
+            AccountType::Individual => Ok(steamid.steam2_with(Steam2Format::GoldSrc)),
+            account_type => Err(Steam2RenderError { account_type }),
+        }
+    }
+
+    pub fn serialize<S>(steamid: &SteamID, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&try_render(*steamid).map_err(ser::Error::custom)?)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SteamID, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let authid = String::deserialize(deserializer)?;
+        SteamID::from_steam2(authid.trim())
+            .map_err(|_| de::Error::custom(format!("Invalid authid: {}", authid)))
+    }
+}
+
+// Database dumps are inconsistent about quoting numbers, so strings are accepted too.
+mod length {
+    use serde::{
+        de::{self, Visitor},
+        Deserializer, Serializer,
+    };
+
+    use super::*;
+
+    pub(super) fn from_seconds(seconds: u64) -> Option<Duration> {
+        if seconds == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(seconds))
+        }
+    }
+
+    // Rounds up, and never gives 0 for a timed ban, since that would make it permanent
+    pub(super) fn to_seconds(duration: Option<Duration>) -> u64 {
+        duration.map_or(0, |d| {
+            let seconds = d.as_secs().saturating_add(u64::from(d.subsec_nanos() > 0));
+            seconds.max(1)
+        })
+    }
+
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(to_seconds(*duration))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LengthVisitor)
+    }
+
+    struct LengthVisitor;
+    impl<'de> Visitor<'de> for LengthVisitor {
+        type Value = Option<Duration>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a ban length in seconds")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Option<Duration>, E>
+        where
+            E: de::Error,
+        {
+            value
+                .trim()
+                .parse()
+                .map(from_seconds)
+                .map_err(|_| E::custom(format!("Invalid ban length: {}", value)))
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Option<Duration>, E>
+        where
+            E: de::Error,
+        {
+            Ok(from_seconds(value))
+        }
+    }
+}
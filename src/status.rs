@@ -0,0 +1,84 @@
+//! Reading the player table out of the srcds `status` console command's output.
+//!
+//! Both the classic layout (`# userid name uniqueid connected ping loss state adr`) and CS:GO's,
+//! which adds a slot column after the userid, are understood:
+//!
+//! ```text
+//! # userid name                uniqueid            connected ping loss state  adr
+//! #      2 "Gabe"              [U:1:22202]         05:42       45    0 active 192.168.1.2:27005
+//! #      3 "Bob"               BOT                                     active
+//! ```
+//!
+//! Bots and players still authenticating show up with the placeholders that
+//! [`SteamID::from_steam2_extended`] understands.
+
+use std::{net::SocketAddr, time::Duration};
+
+use crate::SteamID;
+
+/// One row of the player table.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StatusPlayer {
+    pub userid: u32,
+    pub name: String,
+    pub steamid: SteamID,
+    /// `None` for bots, which don't have connection stats.
+    pub connected: Option<Duration>,
+    pub ping: Option<u32>,
+    /// `None` for bots and the host of a listen server, whose address is `loopback`.
+    pub address: Option<SocketAddr>,
+}
+
+/// Parses every player row in `output`. Everything else, including the header lines and rows
+/// that can't be made sense of, is skipped.
+pub fn parse(output: &str) -> Vec<StatusPlayer> {
+    output.lines().filter_map(parse_row).collect()
+}
+
+fn parse_row(line: &str) -> Option<StatusPlayer> {
+    let line = line.trim().strip_prefix('#')?;
+    // Names may contain quotes themselves, so the name runs to the last one
+    let name_start = line.find('"')?;
+    let name_end = line.rfind('"').filter(|&end| end > name_start)?;
+
+    // The userid, then CS:GO's slot
+    let userid = line[..name_start].split_whitespace().next()?.parse().ok()?;
+    let name = line[name_start + 1..name_end].to_owned();
+
+    let mut fields = line[name_end + 1..].split_whitespace();
+    let uniqueid = fields.next()?;
+    let steamid = SteamID::from_steam3(uniqueid)
+        .or_else(|_| SteamID::from_steam2_extended(uniqueid))
+        .ok()?;
+
+    // Bots only have a state after the uniqueid; everyone else has connected, ping, loss, state
+    // and adr, with CS:GO's rate before the adr
+    let rest: Vec<&str> = fields.collect();
+    let (connected, ping, address) = if rest.len() >= 4 {
+        (
+            parse_connected(rest[0]),
+            rest[1].parse().ok(),
+            rest[4..].last().and_then(|adr| adr.parse().ok()),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    Some(StatusPlayer {
+        userid,
+        name,
+        steamid,
+        connected,
+        ping,
+        address,
+    })
+}
+
+// Parses `mm:ss` or `h:mm:ss`
+fn parse_connected(connected: &str) -> Option<Duration> {
+    let mut seconds = 0u64;
+    for part in connected.split(':') {
+        seconds = seconds.checked_mul(60)?.checked_add(part.parse().ok()?)?;
+    }
+    Some(Duration::from_secs(seconds))
+}
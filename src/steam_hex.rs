@@ -0,0 +1,54 @@
+//! The `steam:<hex>` identifiers used by FiveM and RedM, such as `steam:1100001000056ba`.
+//!
+//! The hex is simply the steam64, in lowercase and without leading zeroes.
+
+use crate::{Cursor, SteamID, SteamIDParseError, SteamIDParseErrorKind};
+
+const PREFIX: &str = "steam:";
+
+impl SteamID {
+    /// The FiveM identifier, e.g. `steam:1100001000056ba`.
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from(76561197960287930);
+    /// assert_eq!(s.steam_hex(), "steam:1100001000056ba");
+    /// assert_eq!(SteamID::from_steam_hex("1100001000056BA"), Ok(s));
+    /// ```
+    pub fn steam_hex(&self) -> String {
+        format!("{}{:x}", PREFIX, self.0)
+    }
+
+    /// Parses a FiveM identifier. The `steam:` prefix is optional, and the hex digits may be in
+    /// either case.
+    pub fn from_steam_hex(s: &str) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::new(s);
+        if s.starts_with(PREFIX) {
+            cursor.pos = PREFIX.len();
+        }
+
+        let start = cursor.pos;
+        let mut steam64 = 0u64;
+        loop {
+            match cursor.peek() {
+                None if cursor.pos > start => break,
+                Some(byte) => match char::from(byte).to_digit(16) {
+                    Some(digit) => {
+                        // More hex digits than fit in a steam64 aren't one
+                        steam64 = steam64
+                            .checked_mul(16)
+                            .map(|v| v | u64::from(digit))
+                            .ok_or_else(|| {
+                                cursor.error_at(SteamIDParseErrorKind::WrongFormat, start)
+                            })?;
+                    }
+                    None => return Err(cursor.unexpected()),
+                },
+                None => return Err(cursor.unexpected()),
+            }
+            cursor.next();
+        }
+
+        Ok(SteamID(steam64))
+    }
+}
@@ -0,0 +1,52 @@
+//! `steam://` deep links that open a profile or group in the Steam client.
+
+use crate::{AccountType, Cursor, SteamID, SteamIDParseError, SteamIDParseErrorKind};
+
+const PREFIXES: &[&str] = &[
+    "steam://friends/add/",
+    "steam://friends/message/",
+    "steam://url/SteamIDPage/",
+    "steam://url/GroupSteamIDPage/",
+];
+
+impl SteamID {
+    /// A `steam://friends/add/<steam64>` link, which opens the "add friend" dialog.
+    pub fn friends_add_url(&self) -> String {
+        format!("steam://friends/add/{}", self.0)
+    }
+
+    /// A `steam://url/SteamIDPage/<steam64>` link, which opens the profile in the client, or
+    /// `steam://url/GroupSteamIDPage/<steam64>` for clans.
+    pub fn steam_id_page_url(&self) -> String {
+        match self.account_type() {
+            AccountType::Clan => format!("steam://url/GroupSteamIDPage/{}", self.0),
+            _ => format!("steam://url/SteamIDPage/{}", self.0),
+        }
+    }
+
+    /// Extracts the steam64 from a `steam://friends/add/`, `steam://friends/message/`,
+    /// `steam://url/SteamIDPage/` or `steam://url/GroupSteamIDPage/` link. For lobby links, see
+    /// [`SteamID::from_lobby_connect`].
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let s = SteamID::from_steam_url("steam://friends/add/76561197960287930");
+    /// assert_eq!(s, Ok(SteamID::from(76561197960287930)));
+    /// ```
+    pub fn from_steam_url(url: &str) -> Result<Self, SteamIDParseError> {
+        let mut cursor = Cursor::new(url);
+        let prefix = PREFIXES
+            .iter()
+            .find(|&&prefix| url.starts_with(prefix))
+            .ok_or_else(|| cursor.error_at(SteamIDParseErrorKind::WrongFormat, 0))?;
+        cursor.pos = prefix.len();
+
+        let (steam64, _) = cursor.number(u64::MAX, SteamIDParseErrorKind::WrongFormat)?;
+        if cursor.peek() == Some(b'/') {
+            cursor.next();
+        }
+        cursor.end()?;
+
+        Ok(SteamID(steam64))
+    }
+}
@@ -0,0 +1,74 @@
+//! Corrections for near-miss steam2 and steam3 IDs, to offer back to the user when parsing
+//! fails. These allocate, so they're kept out of the parsers and only run when asked for.
+//!
+//! ```
+//! # use steamid_ng::{suggest, SteamID};
+//! let input = "STEAM1:0:11101";
+//! if SteamID::from_steam2(input).is_err() {
+//!     assert_eq!(suggest::steam2(input).as_deref(), Some("STEAM_1:0:11101"));
+//! }
+//! ```
+
+use crate::{char_to_account_type, AccountType, SteamID};
+
+/// Rewrites `input` into the canonical `STEAM_X:Y:Z` shape, if it looks like a steam2 ID with a
+/// wrongly cased or separated prefix (`steam_`, `STEAM1`, `STEAM-1`) or `;`/`.` in place of
+/// colons. Returns `None` if the rewritten form still doesn't parse.
+pub fn steam2(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    let prefix = trimmed.get(..5)?;
+    if !prefix.eq_ignore_ascii_case("STEAM") {
+        return None;
+    }
+
+    let rest = trimmed[5..].trim_start_matches(['_', '-', ' ']);
+    let candidate = format!("STEAM_{}", rest.replace([';', '.'], ":"));
+    accept(input, candidate, |s| SteamID::from_steam2_helper(s).is_ok())
+}
+
+/// Rewrites `input` into the canonical `[T:U:A]` shape, if it looks like a steam3 ID with missing
+/// brackets, a lowercased type letter or `;` in place of colons. Returns `None` if the rewritten
+/// form still doesn't parse.
+pub fn steam3(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    let inner = trimmed.strip_prefix('[').unwrap_or(trimmed);
+    let inner = inner.strip_suffix(']').unwrap_or(inner).replace(';', ":");
+
+    let mut chars = inner.chars();
+    let type_char = chars.next()?;
+    // Only touch the type letter if it's wrong as written, since case is significant for some
+    // of them (`c` and `C` are different account types)
+    let type_char = if char_to_account_type(type_char).0 == AccountType::Invalid {
+        type_char.to_ascii_uppercase()
+    } else {
+        type_char
+    };
+
+    let candidate = format!("[{}{}]", type_char, chars.as_str());
+    accept(input, candidate, |s| SteamID::from_steam3_helper(s).is_ok())
+}
+
+/// Suggests a correction for anything `FromStr` rejects: `input` with the surrounding whitespace
+/// removed if that's all that's wrong with it, otherwise a [`steam2`] or [`steam3`] correction.
+pub fn any(input: &str) -> Option<String> {
+    trimmed(input)
+        .or_else(|| steam2(input))
+        .or_else(|| steam3(input))
+}
+
+fn trimmed(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    if trimmed.len() == input.len() || trimmed.parse::<SteamID>().is_err() {
+        return None;
+    }
+
+    Some(trimmed.to_owned())
+}
+
+fn accept(input: &str, candidate: String, parses: fn(&str) -> bool) -> Option<String> {
+    if candidate == input || !parses(&candidate) {
+        return None;
+    }
+
+    Some(candidate)
+}
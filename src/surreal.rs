@@ -0,0 +1,53 @@
+use surrealdb_types::{Error, Kind, Number, SurrealValue, Value};
+
+use crate::SteamID;
+
+/// How to store a SteamID in a SurrealDB value.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+pub enum SurrealRepr {
+    /// An `int` holding the steam64. SurrealDB integers are signed, so the steam64 is stored
+    /// bit-for-bit; this only matters for universes above 127, which don't exist.
+    #[default]
+    Number,
+    /// A `string` holding the steam64 in decimal, for clients that would otherwise round it
+    /// through a float (JavaScript, mostly).
+    String,
+}
+
+impl SteamID {
+    pub fn to_surreal_value(&self, repr: SurrealRepr) -> Value {
+        match repr {
+            SurrealRepr::Number => Value::Number(Number::Int(self.0 as i64)),
+            SurrealRepr::String => Value::String(self.0.to_string()),
+        }
+    }
+}
+
+/// Converts to an `int` (see [`SurrealRepr::Number`]). Both `int` and `string` values are accepted
+/// when converting back; strings can be in any format `FromStr` understands.
+impl SurrealValue for SteamID {
+    fn kind_of() -> Kind {
+        Kind::Either(vec![Kind::Int, Kind::String])
+    }
+
+    fn is_value(value: &Value) -> bool {
+        matches!(value, Value::Number(Number::Int(_)) | Value::String(_))
+    }
+
+    fn into_value(self) -> Value {
+        self.to_surreal_value(SurrealRepr::Number)
+    }
+
+    fn from_value(value: Value) -> Result<Self, Error> {
+        match value {
+            Value::Number(Number::Int(n)) => Ok(SteamID(n as u64)),
+            Value::String(s) => s
+                .parse()
+                .map_err(|_| Error::validation(format!("Invalid SteamID: {}", s), None)),
+            other => Err(Error::validation(
+                format!("Expected a SteamID, found {:?}", other.kind()),
+                None,
+            )),
+        }
+    }
+}
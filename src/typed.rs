@@ -0,0 +1,318 @@
+//! SteamIDs restricted to one kind of account, for APIs that only make sense for users, groups
+//! and so on. They convert to and from [`SteamID`], parse and (de)serialize like it, and reject
+//! IDs of any other kind with [`SteamIDParseErrorKind::InvalidAccountType`] (or
+//! [`SteamIDParseErrorKind::InvalidInstance`], for chats that aren't lobbies).
+
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{AccountType, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe};
+
+macro_rules! typed_steamid {
+    ($(#[$meta:meta])* $name:ident, |$id:ident| $check:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+        pub struct $name(SteamID);
+
+        impl $name {
+            pub fn steamid(&self) -> SteamID {
+                self.0
+            }
+        }
+
+        impl TryFrom<SteamID> for $name {
+            type Error = SteamIDParseError;
+
+            fn try_from($id: SteamID) -> Result<Self, Self::Error> {
+                let check: Result<(), SteamIDParseErrorKind> = $check;
+                check.map(|()| $name($id)).map_err(SteamIDParseError::new)
+            }
+        }
+
+        impl From<$name> for SteamID {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0.into()
+            }
+        }
+
+        /// Formats like [`SteamID`]'s `Display`.
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        /// Parses any format `SteamID`'s `FromStr` does.
+        impl FromStr for $name {
+            type Err = SteamIDParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::try_from(s.parse::<SteamID>()?)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                $name::try_from(SteamID::deserialize(deserializer)?).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+fn require(steamid: SteamID, account_type: AccountType) -> Result<(), SteamIDParseErrorKind> {
+    if steamid.account_type() == account_type {
+        Ok(())
+    } else {
+        Err(SteamIDParseErrorKind::InvalidAccountType)
+    }
+}
+
+typed_steamid!(
+    /// The SteamID of a user account.
+    ///
+    /// ```
+    /// # use steamid_ng::{IndividualID, SteamID};
+    /// let id: IndividualID = "STEAM_1:0:11101".parse().unwrap();
+    /// assert_eq!(id.steam2(), "STEAM_1:0:11101");
+    /// assert!("[g:1:4]".parse::<IndividualID>().is_err());
+    /// ```
+    IndividualID,
+    |steamid| require(steamid, AccountType::Individual)
+);
+
+impl IndividualID {
+    /// The desktop account in the public universe with the given account id, as
+    /// [`SteamID::from_steam32`] makes.
+    pub fn new(account_id: u32) -> Self {
+        IndividualID(SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        ))
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.0.account_id()
+    }
+
+    /// [`SteamID::steam2`], which never falls back to the steam64 for individuals.
+    pub fn steam2(&self) -> String {
+        self.0.steam2()
+    }
+
+    pub fn steam3(&self) -> String {
+        self.0.steam3()
+    }
+}
+
+typed_steamid!(
+    /// The SteamID of a Steam group (clan).
+    ///
+    /// ```
+    /// # use steamid_ng::ClanID;
+    /// let id: ClanID = "[g:1:4]".parse().unwrap();
+    /// let url = "https://steamcommunity.com/gid/103582791429521412";
+    /// assert_eq!(ClanID::from_group_url(url), Ok(id));
+    /// assert!("[U:1:22202]".parse::<ClanID>().is_err());
+    /// ```
+    ClanID,
+    |steamid| require(steamid, AccountType::Clan)
+);
+
+impl ClanID {
+    /// The clan in the public universe with the given account id.
+    pub fn new(account_id: u32) -> Self {
+        ClanID(SteamID::new(
+            account_id,
+            Instance::All,
+            AccountType::Clan,
+            Universe::Public,
+        ))
+    }
+
+    /// See [`SteamID::from_group_url`].
+    pub fn from_group_url(url: &str) -> Result<Self, SteamIDParseError> {
+        SteamID::from_group_url(url).map(ClanID)
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.0.account_id()
+    }
+
+    /// [`SteamID::group_url`], which always has one for clans.
+    pub fn group_url(&self) -> String {
+        self.0.group_url().expect("Clans have a group URL")
+    }
+
+    pub fn steam3(&self) -> String {
+        self.0.steam3()
+    }
+}
+
+typed_steamid!(
+    /// The SteamID of a persistent game server account, the kind created with a game server login
+    /// token.
+    ///
+    /// ```
+    /// # use steamid_ng::GameServerID;
+    /// let id: GameServerID = "[G:1:1234]".parse().unwrap();
+    /// assert_eq!(id, GameServerID::new(1234));
+    /// ```
+    GameServerID,
+    |steamid| require(steamid, AccountType::GameServer)
+);
+
+impl GameServerID {
+    /// The game server account in the public universe with the given account id.
+    pub fn new(account_id: u32) -> Self {
+        GameServerID(SteamID::new(
+            account_id,
+            Instance::All,
+            AccountType::GameServer,
+            Universe::Public,
+        ))
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.0.account_id()
+    }
+
+    pub fn steam3(&self) -> String {
+        self.0.steam3()
+    }
+}
+
+typed_steamid!(
+    /// The SteamID of an anonymous game server, which Steam assigns for a single session.
+    ///
+    /// Unlike other accounts, these are told apart by their instance as well as their account id,
+    /// so it can't be left out: [`AnonGameServerID::new`] takes one, and the steam3 form always
+    /// includes it. The instance is an arbitrary 20-bit number rather than one of [`Instance`]'s
+    /// values, see [`AnonGameServerID::instance_id`].
+    ///
+    /// ```
+    /// # use steamid_ng::AnonGameServerID;
+    /// let id: AnonGameServerID = "[A:1:1234:5678]".parse().unwrap();
+    /// assert_eq!(id, AnonGameServerID::new(1234, 5678).unwrap());
+    /// assert_eq!(id.instance_id(), 5678);
+    /// ```
+    AnonGameServerID,
+    |steamid| require(steamid, AccountType::AnonGameServer)
+);
+
+impl AnonGameServerID {
+    /// The largest instance an ID has room for.
+    pub const MAX_INSTANCE_ID: u32 = SteamID::ACCOUNT_INSTANCE_MASK;
+
+    /// The anonymous game server in the public universe with the given account id and instance,
+    /// or `None` if `instance_id` is above [`AnonGameServerID::MAX_INSTANCE_ID`].
+    pub fn new(account_id: u32, instance_id: u32) -> Option<Self> {
+        if instance_id > Self::MAX_INSTANCE_ID {
+            return None;
+        }
+
+        let mut steamid = SteamID::new(
+            account_id,
+            Instance::All,
+            AccountType::AnonGameServer,
+            Universe::Public,
+        );
+        steamid.0 |= u64::from(instance_id) << 32;
+        Some(AnonGameServerID(steamid))
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.0.account_id()
+    }
+
+    /// The raw instance bits. [`SteamID::instance`] would report most of them as
+    /// [`Instance::Invalid`].
+    pub fn instance_id(&self) -> u32 {
+        self.0.instance_value()
+    }
+
+    /// The steam3 form, instance included.
+    pub fn steam3(&self) -> String {
+        self.0.steam3()
+    }
+}
+
+typed_steamid!(
+    /// The SteamID of a lobby: a chat account with the lobby instance flag set, and also the
+    /// matchmaking flag if it was created through the matchmaking API, as game lobbies are.
+    ///
+    /// ```
+    /// # use steamid_ng::LobbyID;
+    /// let id: LobbyID = "109775241017770404".parse().unwrap();
+    /// assert_eq!(id, LobbyID::from_lobby_index(100614564));
+    /// assert!(id.is_mms_lobby());
+    /// assert_eq!(id.steam3(), "[L:1:100614564:393216]");
+    /// ```
+    LobbyID,
+    |steamid| require_lobby(steamid)
+);
+
+fn require_lobby(steamid: SteamID) -> Result<(), SteamIDParseErrorKind> {
+    require(steamid, AccountType::Chat)?;
+    if steamid.chat_instance_flags() & SteamID::CHAT_INSTANCE_FLAG_LOBBY == 0 {
+        return Err(SteamIDParseErrorKind::InvalidInstance);
+    }
+    Ok(())
+}
+
+impl LobbyID {
+    /// The matchmaking lobby in the public universe with the given account id, with both lobby
+    /// flags set as `ISteamMatchmaking::CreateLobby` sets them.
+    pub fn from_lobby_index(index: u32) -> Self {
+        let mut steamid = SteamID::new(index, Instance::All, AccountType::Chat, Universe::Public);
+        let flags = SteamID::CHAT_INSTANCE_FLAG_LOBBY | SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY;
+        steamid.0 |= u64::from(flags) << 32;
+        LobbyID(steamid)
+    }
+
+    /// See [`SteamID::from_lobby_connect`].
+    pub fn from_lobby_connect(connect: &str) -> Result<Self, SteamIDParseError> {
+        SteamID::from_lobby_connect(connect).map(LobbyID)
+    }
+
+    /// The account id, which numbers lobbies.
+    pub fn lobby_index(&self) -> u32 {
+        self.0.account_id()
+    }
+
+    /// Whether the lobby was created through the matchmaking API rather than as a plain lobby
+    /// chat.
+    pub fn is_mms_lobby(&self) -> bool {
+        self.0.chat_instance_flags() & SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY != 0
+    }
+
+    /// The steam3 form. Matchmaking lobbies are written with their whole instance, such as
+    /// `[L:1:100614564:393216]`, so parsing it back gives the same matchmaking lobby.
+    pub fn steam3(&self) -> String {
+        self.0.steam3()
+    }
+}
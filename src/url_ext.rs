@@ -0,0 +1,53 @@
+//! Community URLs as `url::Url`s.
+
+use url::Url;
+
+use crate::{SteamID, SteamIDParseError};
+
+impl SteamID {
+    /// Parses a steamcommunity.com profile (`/profiles/`), group (`/gid/`) or trade offer
+    /// (`/tradeoffer/new/`) URL, like [`SteamID::from_community_url`],
+    /// [`SteamID::from_group_url`] and [`SteamID::from_trade_url`] do. Error positions refer to
+    /// [`Url::as_str`].
+    ///
+    /// ```
+    /// # use steamid_ng::SteamID;
+    /// let url = url::Url::parse("https://steamcommunity.com/profiles/76561197960287930").unwrap();
+    /// assert_eq!(SteamID::from_url(&url), Ok(SteamID::from(76561197960287930)));
+    /// assert_eq!(SteamID::from(76561197960287930).to_url(), url);
+    /// ```
+    pub fn from_url(url: &Url) -> Result<Self, SteamIDParseError> {
+        let path = url.path();
+        if path.starts_with("/gid/") {
+            SteamID::from_group_url(url.as_str())
+        } else if path.starts_with("/tradeoffer/") {
+            SteamID::from_trade_url(url.as_str())
+        } else {
+            SteamID::from_community_url(url.as_str())
+        }
+    }
+
+    /// [`SteamID::community_url`] as a `Url`.
+    pub fn to_url(&self) -> Url {
+        Url::parse(&self.community_url()).expect("Community URLs are valid")
+    }
+
+    /// [`SteamID::group_url`] as a `Url`.
+    pub fn to_group_url(&self) -> Option<Url> {
+        self.group_url()
+            .map(|url| Url::parse(&url).expect("Group URLs are valid"))
+    }
+
+    /// A trade offer URL for an individual with the trade `token` from their privacy settings,
+    /// or `None` for other account types.
+    pub fn to_trade_url(&self, token: &str) -> Option<Url> {
+        let partner = self.trade_url_partner_param()?;
+        let mut url = Url::parse(&format!(
+            "https://steamcommunity.com/tradeoffer/new/?{}",
+            partner
+        ))
+        .expect("Trade URLs are valid");
+        url.query_pairs_mut().append_pair("token", token);
+        Some(url)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{AccountType, Instance, SteamID, SteamIDParseError, SteamIDParseErrorKind, Universe};
+
+/// How much of a SteamID [`SteamID::validate`] checks. Protocol code relaying IDs can stick to
+/// the field ranges, while code handling user input can hold IDs to Valve's rules.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ValidationLevel {
+    /// Every field has a value Steam knows about, as [`SteamID::from_steam64`] checks.
+    #[default]
+    BitfieldsOnly,
+    /// The fields also make sense together, as [`SteamID::is_valid`] checks.
+    Semantic,
+    /// The ID is also in the form Steam itself hands out: individuals have the desktop instance,
+    /// and console users the console instance. See [`SteamID::normalize`].
+    Strict,
+}
+
+impl SteamID {
+    /// Checks this ID against `level`.
+    ///
+    /// ```
+    /// # use steamid_ng::{SteamID, SteamIDParseErrorKind, ValidationLevel};
+    /// let web = SteamID::from_steam3("[U:1:22202:4]").unwrap();
+    /// assert!(web.validate(ValidationLevel::Semantic).is_ok());
+    /// assert_eq!(
+    ///     web.validate(ValidationLevel::Strict).unwrap_err().kind(),
+    ///     SteamIDParseErrorKind::InvalidInstance
+    /// );
+    /// ```
+    pub fn validate(&self, level: ValidationLevel) -> Result<(), SteamIDParseError> {
+        Self::from_steam64(self.0)?;
+        if level == ValidationLevel::BitfieldsOnly {
+            return Ok(());
+        }
+
+        if let Some(kind) = self.validity_error() {
+            return Err(SteamIDParseError::new(kind));
+        }
+        if level == ValidationLevel::Strict && self.normalize() != *self {
+            return Err(SteamIDParseError::new(
+                SteamIDParseErrorKind::InvalidInstance,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Like [`SteamID::from_steam64`], but checks the ID against `level`.
+    pub fn from_steam64_with(
+        steam64: u64,
+        level: ValidationLevel,
+    ) -> Result<Self, SteamIDParseError> {
+        let steamid = SteamID(steam64);
+        steamid.validate(level)?;
+        Ok(steamid)
+    }
+
+    /// Like [`SteamID::try_new`], but checks the ID against `level`.
+    pub fn try_new_with(
+        account_id: u32,
+        instance: Instance,
+        account_type: AccountType,
+        universe: Universe,
+        level: ValidationLevel,
+    ) -> Result<Self, SteamIDParseError> {
+        let steamid = Self::new_unchecked(account_id, instance, account_type, universe);
+        steamid.validate(level)?;
+        Ok(steamid)
+    }
+}
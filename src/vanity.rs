@@ -0,0 +1,260 @@
+//! Pluggable vanity URL resolution.
+//!
+//! [`VanityResolver`] and [`BlockingVanityResolver`] abstract over whatever actually looks names
+//! up: the Steam Web API (`webapi::WebApi`, with the `webapi` feature), a database, or a proxy
+//! service. [`CachedResolver`] puts a TTL cache in front of any of them, and
+//! [`resolve_community_url`] uses one to turn any community URL into a SteamID.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{community::community_path, Cursor, SteamID, SteamIDParseError};
+
+/// What kind of vanity URL to look up. The values are the Web API's `url_type`.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum VanityType {
+    /// `steamcommunity.com/id/<name>`
+    Individual = 1,
+    /// `steamcommunity.com/groups/<name>`
+    Group = 2,
+    /// `steamcommunity.com/games/<name>`, official game groups
+    GameGroup = 3,
+}
+
+/// What [`VanityResolver::resolve`] returns. It's `Send`, except on wasm32, where browser
+/// futures can't be sent between threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub type ResolveFuture<'a, E> =
+    Pin<Box<dyn Future<Output = Result<Option<SteamID>, E>> + Send + 'a>>;
+#[cfg(target_arch = "wasm32")]
+pub type ResolveFuture<'a, E> = Pin<Box<dyn Future<Output = Result<Option<SteamID>, E>> + 'a>>;
+
+/// Looks up vanity names asynchronously. `Ok(None)` means nothing is registered under the name.
+pub trait VanityResolver {
+    type Error;
+
+    fn resolve<'a>(
+        &'a self,
+        vanity: &'a str,
+        vanity_type: VanityType,
+    ) -> ResolveFuture<'a, Self::Error>;
+}
+
+/// Looks up vanity names synchronously. `Ok(None)` means nothing is registered under the name.
+pub trait BlockingVanityResolver {
+    type Error;
+
+    fn resolve_blocking(
+        &self,
+        vanity: &str,
+        vanity_type: VanityType,
+    ) -> Result<Option<SteamID>, Self::Error>;
+}
+
+type CacheEntries = HashMap<(VanityType, String), (Instant, Option<SteamID>)>;
+
+/// Caches another resolver's answers, including "no such name", for a fixed time. Vanity names
+/// are case-insensitive, so `GabeLoganNewell` and `gabelogannewell` share an entry.
+///
+/// Expired entries are only replaced when looked up again; call
+/// [`purge_expired`](CachedResolver::purge_expired) now and then if the set of names is unbounded.
+///
+/// Entries are timed with `std::time::Instant`, which panics on `wasm32-unknown-unknown`, so this
+/// can't be used in the browser.
+#[derive(Debug)]
+pub struct CachedResolver<R> {
+    inner: R,
+    ttl: Duration,
+    entries: Mutex<CacheEntries>,
+}
+
+impl<R> CachedResolver<R> {
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        CachedResolver {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn purge_expired(&self) {
+        let ttl = self.ttl;
+        self.lock().retain(|_, (at, _)| at.elapsed() < ttl);
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheEntries> {
+        // The map can't be left half-updated, so a panic elsewhere doesn't matter
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn cached(&self, key: &(VanityType, String)) -> Option<Option<SteamID>> {
+        let entries = self.lock();
+        let &(at, steamid) = entries.get(key)?;
+        if at.elapsed() < self.ttl {
+            Some(steamid)
+        } else {
+            None
+        }
+    }
+
+    fn store(&self, key: (VanityType, String), steamid: Option<SteamID>) {
+        self.lock().insert(key, (Instant::now(), steamid));
+    }
+}
+
+fn cache_key(vanity: &str, vanity_type: VanityType) -> (VanityType, String) {
+    (vanity_type, vanity.to_lowercase())
+}
+
+impl<R: VanityResolver + Sync> VanityResolver for CachedResolver<R> {
+    type Error = R::Error;
+
+    fn resolve<'a>(
+        &'a self,
+        vanity: &'a str,
+        vanity_type: VanityType,
+    ) -> ResolveFuture<'a, Self::Error> {
+        Box::pin(async move {
+            let key = cache_key(vanity, vanity_type);
+            if let Some(steamid) = self.cached(&key) {
+                return Ok(steamid);
+            }
+
+            let steamid = self.inner.resolve(vanity, vanity_type).await?;
+            self.store(key, steamid);
+            Ok(steamid)
+        })
+    }
+}
+
+impl<R: BlockingVanityResolver> BlockingVanityResolver for CachedResolver<R> {
+    type Error = R::Error;
+
+    fn resolve_blocking(
+        &self,
+        vanity: &str,
+        vanity_type: VanityType,
+    ) -> Result<Option<SteamID>, Self::Error> {
+        let key = cache_key(vanity, vanity_type);
+        if let Some(steamid) = self.cached(&key) {
+            return Ok(steamid);
+        }
+
+        let steamid = self.inner.resolve_blocking(vanity, vanity_type)?;
+        self.store(key, steamid);
+        Ok(steamid)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolveError<E> {
+    /// The URL isn't a steamcommunity.com profile or group URL.
+    InvalidUrl(SteamIDParseError),
+    /// Nothing is registered under the URL's vanity name.
+    NoMatch,
+    Resolver(E),
+}
+
+impl<E: Error + 'static> Error for ResolveError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ResolveError::InvalidUrl(e) => Some(e),
+            ResolveError::NoMatch => None,
+            ResolveError::Resolver(e) => Some(e),
+        }
+    }
+}
+
+impl<E: Display> Display for ResolveError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::InvalidUrl(e) => write!(f, "Not a community URL: {}", e),
+            ResolveError::NoMatch => write!(f, "No such vanity URL"),
+            ResolveError::Resolver(e) => write!(f, "Vanity lookup failed: {}", e),
+        }
+    }
+}
+
+/// A community URL, split into either the ID it contains or the vanity name to look up.
+enum CommunityUrl<'a> {
+    Id(SteamID),
+    Vanity(&'a str, VanityType),
+}
+
+fn parse_community_url(url: &str) -> Result<CommunityUrl<'_>, SteamIDParseError> {
+    let path = community_path(url);
+    let vanity = path.and_then(|path| {
+        [
+            ("id/", VanityType::Individual),
+            ("groups/", VanityType::Group),
+            ("games/", VanityType::GameGroup),
+        ]
+        .iter()
+        .find_map(|&(section, vanity_type)| Some((path.strip_prefix(section)?, vanity_type)))
+    });
+
+    let (rest, vanity_type) = match vanity {
+        Some(vanity) => vanity,
+        None => {
+            return SteamID::from_community_url(url)
+                .or_else(|_| SteamID::from_group_url(url))
+                .map(CommunityUrl::Id)
+        }
+    };
+
+    let name = rest.split(&['/', '?', '#'][..]).next().unwrap_or_default();
+    if name.is_empty() {
+        let mut cursor = Cursor::new(url);
+        cursor.pos = url.len() - rest.len();
+        return Err(cursor.unexpected());
+    }
+
+    Ok(CommunityUrl::Vanity(name, vanity_type))
+}
+
+/// Resolves any profile or group URL on steamcommunity.com with `resolver`. `/profiles/` and
+/// `/gid/` URLs already contain the ID and are parsed without asking the resolver; `/id/`,
+/// `/groups/` and `/games/` vanity URLs are looked up.
+pub async fn resolve_community_url<R: VanityResolver>(
+    resolver: &R,
+    url: &str,
+) -> Result<SteamID, ResolveError<R::Error>> {
+    match parse_community_url(url).map_err(ResolveError::InvalidUrl)? {
+        CommunityUrl::Id(steamid) => Ok(steamid),
+        CommunityUrl::Vanity(name, vanity_type) => resolver
+            .resolve(name, vanity_type)
+            .await
+            .map_err(ResolveError::Resolver)?
+            .ok_or(ResolveError::NoMatch),
+    }
+}
+
+/// The blocking counterpart of [`resolve_community_url`].
+pub fn resolve_community_url_blocking<R: BlockingVanityResolver>(
+    resolver: &R,
+    url: &str,
+) -> Result<SteamID, ResolveError<R::Error>> {
+    match parse_community_url(url).map_err(ResolveError::InvalidUrl)? {
+        CommunityUrl::Id(steamid) => Ok(steamid),
+        CommunityUrl::Vanity(name, vanity_type) => resolver
+            .resolve_blocking(name, vanity_type)
+            .map_err(ResolveError::Resolver)?
+            .ok_or(ResolveError::NoMatch),
+    }
+}
@@ -0,0 +1,148 @@
+//! Pulling SteamIDs out of Valve's KeyValues (VDF) text, such as the Steam client's
+//! `loginusers.vdf` or server configs.
+//!
+//! ```
+//! # use steamid_ng::{vdf, SteamID};
+//! let loginusers = r#"
+//! "users"
+//! {
+//!     "76561197960287930"
+//!     {
+//!         "AccountName"    "gabelogannewell"
+//!         "MostRecent"     "1"
+//!         "Timestamp"      "1700000000"
+//!     }
+//! }
+//! "#;
+//! let found = vdf::find_steamids(loginusers);
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].path, ["users", "76561197960287930"]);
+//! assert_eq!(found[0].steamid, SteamID::from(76561197960287930));
+//! ```
+
+use crate::{SteamID, Universe};
+
+/// A SteamID found in a VDF document.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct VdfEntry {
+    /// The keys leading to the ID, outermost first. If the ID is a key itself (as the accounts in
+    /// `loginusers.vdf` are), it's the last one; if it's a value, the last one is its key.
+    pub path: Vec<String>,
+    pub steamid: SteamID,
+}
+
+/// Finds every key and value in `text` that is a SteamID, in document order. Steam2 and steam3
+/// IDs are recognized, as are steam64s if [`SteamID::from_steam64`] accepts them with a universe
+/// other than [`Universe::Invalid`], which rules out timestamps and other small numbers.
+///
+/// This is deliberately forgiving: malformed documents (unbalanced braces, a key without a value)
+/// are read as far as they make sense, and never produce an error.
+pub fn find_steamids(text: &str) -> Vec<VdfEntry> {
+    let mut found = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut key: Option<String> = None;
+
+    let mut check = |path: &[String], last: &str, token: &str| {
+        if let Some(steamid) = as_steamid(token) {
+            let mut path = path.to_vec();
+            path.push(last.to_owned());
+            found.push(VdfEntry { path, steamid });
+        }
+    };
+
+    for token in tokenize(text) {
+        match token {
+            Token::Str(s) => match key.take() {
+                None => key = Some(s),
+                Some(k) => {
+                    check(&path, &k, &k);
+                    check(&path, &k, &s);
+                }
+            },
+            Token::Open => {
+                let k = key.take().unwrap_or_default();
+                check(&path, &k, &k);
+                path.push(k);
+            }
+            Token::Close => {
+                key = None;
+                path.pop();
+            }
+        }
+    }
+
+    found
+}
+
+fn as_steamid(token: &str) -> Option<SteamID> {
+    if !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+        return token
+            .parse()
+            .ok()
+            .and_then(|steam64| SteamID::from_steam64(steam64).ok())
+            .filter(|steamid| steamid.universe() != Universe::Invalid);
+    }
+
+    SteamID::from_steam2_helper(token)
+        .or_else(|_| SteamID::from_steam3_helper(token))
+        .ok()
+}
+
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut s = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => s.push('\n'),
+                            Some('t') => s.push('\t'),
+                            Some(c) => s.push(c),
+                            None => break,
+                        },
+                        c => s.push(c),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => (),
+            c => {
+                let mut s = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '"' | '{' | '}') {
+                        break;
+                    }
+                    s.push(c);
+                    chars.next();
+                }
+                // Platform conditionals like `[$WIN32]` qualify the pair before them, and aren't
+                // tokens of their own
+                if !s.starts_with("[$") && !s.starts_with("[!$") {
+                    tokens.push(Token::Str(s));
+                }
+            }
+        }
+    }
+
+    tokens
+}
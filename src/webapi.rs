@@ -0,0 +1,387 @@
+//! Resolving vanity URLs and looking up group memberships through the Steam Web API.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), steamid_ng::webapi::WebApiError> {
+//! use steamid_ng::{webapi::WebApi, SteamID};
+//!
+//! let api = WebApi::new("<your api key>");
+//! let gabe = api
+//!     .resolve_community_url("https://steamcommunity.com/id/gabelogannewell")
+//!     .await?;
+//! assert_eq!(gabe, SteamID::resolve_vanity("<your api key>", "gabelogannewell").await?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The module also builds for `wasm32-unknown-unknown`, where reqwest sends requests with the
+//! browser's `fetch`. The browser then decides on proxies, certificates and timeouts, so
+//! [`WebApiBuilder`] has no settings for them there.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use reqwest::StatusCode;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::{Certificate, Proxy};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+
+pub use crate::vanity::VanityType;
+use crate::{
+    vanity::{self, ResolveError, ResolveFuture, VanityResolver},
+    ClanID, SteamID, SteamIDParseError,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.steampowered.com";
+
+// ResolveVanityURL's `success` value for names that don't exist
+const NO_MATCH: u32 = 42;
+
+#[derive(Debug)]
+pub enum WebApiError {
+    /// The request failed, or the response wasn't what the Web API method returns. The error's
+    /// URL is removed, since it would contain the API key.
+    Http(reqwest::Error),
+    /// Nothing is registered under that name.
+    NoMatch,
+    /// The Web API reported some other failure.
+    Api {
+        success: u32,
+        message: Option<String>,
+    },
+    /// The URL passed to [`WebApi::resolve_community_url`] isn't a community URL.
+    InvalidUrl(SteamIDParseError),
+}
+
+impl Error for WebApiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WebApiError::Http(e) => Some(e),
+            WebApiError::InvalidUrl(e) => Some(e),
+            WebApiError::NoMatch | WebApiError::Api { .. } => None,
+        }
+    }
+}
+
+impl Display for WebApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WebApiError::Http(e) => write!(f, "Steam Web API request failed: {}", e),
+            WebApiError::NoMatch => write!(f, "No such vanity URL"),
+            WebApiError::Api {
+                success,
+                message: Some(message),
+            } => write!(f, "Steam Web API error {}: {}", success, message),
+            WebApiError::Api {
+                success,
+                message: None,
+            } => write!(f, "Steam Web API error {}", success),
+            WebApiError::InvalidUrl(e) => write!(f, "Not a community URL: {}", e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for WebApiError {
+    fn from(e: reqwest::Error) -> Self {
+        WebApiError::Http(e.without_url())
+    }
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    response: T,
+}
+
+#[derive(Deserialize)]
+struct ResolveVanityResponse {
+    success: u32,
+    steamid: Option<SteamID>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UserGroupListResponse {
+    success: bool,
+    #[serde(default)]
+    groups: Vec<Group>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Group {
+    // The clan's account id, as a string
+    #[serde(deserialize_with = "account_id_from_str")]
+    gid: u32,
+}
+
+fn account_id_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let gid = <&str>::deserialize(deserializer)?;
+    gid.parse().map_err(serde::de::Error::custom)
+}
+
+/// A Web API client. Cloning is cheap, as the underlying `reqwest::Client` is shared.
+///
+/// A client can hold several API keys, see [`WebApi::add_key`]. Clones share which key is in
+/// use, so a key that's been rate limited is skipped by all of them.
+#[derive(Clone)]
+pub struct WebApi {
+    client: reqwest::Client,
+    api_keys: Vec<String>,
+    current_key: Arc<AtomicUsize>,
+    base_url: String,
+}
+
+// Leaves out the API keys, so clients can be logged
+impl fmt::Debug for WebApi {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebApi")
+            .field("client", &self.client)
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebApi {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), api_key)
+    }
+
+    /// Configures the HTTP client first, e.g. to go through a proxy or trust a private root
+    /// certificate.
+    ///
+    /// ```no_run
+    /// # fn run() -> Result<(), steamid_ng::webapi::WebApiError> {
+    /// use std::time::Duration;
+    /// use steamid_ng::webapi::{Proxy, WebApi};
+    ///
+    /// let api = WebApi::builder()
+    ///     .proxy(Proxy::all("http://relay.example.com:3128")?)
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build("<your api key>")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> WebApiBuilder {
+        WebApiBuilder {
+            client: reqwest::Client::builder(),
+        }
+    }
+
+    /// Uses an existing `reqwest::Client`, e.g. to share its connection pool. See
+    /// [`WebApi::builder`] for the common transport settings.
+    pub fn with_client(client: reqwest::Client, api_key: impl Into<String>) -> Self {
+        WebApi {
+            client,
+            api_keys: vec![api_key.into()],
+            current_key: Arc::new(AtomicUsize::new(0)),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+        }
+    }
+
+    /// Adds a key to fail over to. When Steam answers a request with 429 Too Many Requests (the
+    /// key's quota is used up) or 403 Forbidden (the key was revoked), the request is retried
+    /// with the next key, which is then used from there on. Once every key has been tried, the
+    /// last response's error is returned.
+    pub fn add_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_keys.push(api_key.into());
+        self
+    }
+
+    /// Sends requests somewhere other than [`DEFAULT_BASE_URL`], such as a caching proxy.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Looks up `vanity` with `ISteamUser/ResolveVanityURL`.
+    pub async fn resolve_vanity(
+        &self,
+        vanity: &str,
+        vanity_type: VanityType,
+    ) -> Result<SteamID, WebApiError> {
+        let url_type = (vanity_type as u8).to_string();
+        let envelope: Envelope<ResolveVanityResponse> = self
+            .get(
+                "ISteamUser/ResolveVanityURL/v1/",
+                &[("vanityurl", vanity), ("url_type", &url_type)],
+            )
+            .await?;
+
+        match envelope.response {
+            ResolveVanityResponse {
+                success: 1,
+                steamid: Some(steamid),
+                ..
+            } => Ok(steamid),
+            ResolveVanityResponse {
+                success: NO_MATCH, ..
+            } => Err(WebApiError::NoMatch),
+            ResolveVanityResponse {
+                success, message, ..
+            } => Err(WebApiError::Api { success, message }),
+        }
+    }
+
+    /// Lists the groups `steamid` is a member of with `ISteamUser/GetUserGroupList`. Private
+    /// profiles and unknown users are reported as [`WebApiError::Api`] with a `success` of 0, as
+    /// this method only says whether it succeeded.
+    pub async fn get_user_group_list(&self, steamid: SteamID) -> Result<Vec<ClanID>, WebApiError> {
+        let steamid = u64::from(steamid).to_string();
+        let envelope: Envelope<UserGroupListResponse> = self
+            .get("ISteamUser/GetUserGroupList/v1/", &[("steamid", &steamid)])
+            .await?;
+
+        match envelope.response {
+            UserGroupListResponse {
+                success: true,
+                groups,
+                ..
+            } => Ok(groups
+                .into_iter()
+                .map(|group| ClanID::new(group.gid))
+                .collect()),
+            UserGroupListResponse { error, .. } => Err(WebApiError::Api {
+                success: 0,
+                message: error,
+            }),
+        }
+    }
+
+    /// Resolves any profile or group URL on steamcommunity.com. `/profiles/` and `/gid/` URLs
+    /// already contain the ID and are parsed without a request; `/id/`, `/groups/` and `/games/`
+    /// vanity URLs are looked up.
+    pub async fn resolve_community_url(&self, url: &str) -> Result<SteamID, WebApiError> {
+        vanity::resolve_community_url(self, url)
+            .await
+            .map_err(|e| match e {
+                ResolveError::InvalidUrl(e) => WebApiError::InvalidUrl(e),
+                ResolveError::NoMatch => WebApiError::NoMatch,
+                ResolveError::Resolver(e) => e,
+            })
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, WebApiError> {
+        let url = format!("{}/{}", self.base_url.trim_end_matches('/'), method);
+        let mut attempts = self.api_keys.len();
+        loop {
+            let index = self.current_key.load(Ordering::Relaxed) % self.api_keys.len();
+            let response = self
+                .client
+                .get(&url)
+                .query(&[("key", self.api_keys[index].as_str())])
+                .query(query)
+                .send()
+                .await?;
+            attempts -= 1;
+
+            if matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN
+            ) {
+                // Another request may have moved on from this key already
+                let next = (index + 1) % self.api_keys.len();
+                let _ = self.current_key.compare_exchange(
+                    index,
+                    next,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                );
+                if attempts > 0 {
+                    continue;
+                }
+            }
+            return Ok(response.error_for_status()?.json().await?);
+        }
+    }
+}
+
+/// The transport settings for a [`WebApi`], wrapping a `reqwest::ClientBuilder`.
+#[derive(Debug)]
+#[must_use]
+pub struct WebApiBuilder {
+    client: reqwest::ClientBuilder,
+}
+
+impl WebApiBuilder {
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Sends requests through `proxy`. Can be called more than once, the first proxy matching a
+    /// URL is used.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.client = self.client.proxy(proxy);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Ignores the proxies set in the environment (`HTTPS_PROXY` and friends), which are used
+    /// by default.
+    pub fn no_proxy(mut self) -> Self {
+        self.client = self.client.no_proxy();
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Trusts `certificate` as a root, e.g. for a TLS-intercepting corporate proxy.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.client = self.client.add_root_certificate(certificate);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Gives up on connecting after `timeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.client = self.client.connect_timeout(timeout);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Gives up on a request after `timeout`, from connecting until the response body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.client = self.client.timeout(timeout);
+        self
+    }
+
+    /// Builds the client, failing if e.g. the TLS backend can't be initialized.
+    pub fn build(self, api_key: impl Into<String>) -> Result<WebApi, WebApiError> {
+        Ok(WebApi::with_client(self.client.build()?, api_key))
+    }
+}
+
+/// Never fails with [`WebApiError::NoMatch`], reporting unknown names as `Ok(None)` instead.
+impl VanityResolver for WebApi {
+    type Error = WebApiError;
+
+    fn resolve<'a>(
+        &'a self,
+        vanity: &'a str,
+        vanity_type: VanityType,
+    ) -> ResolveFuture<'a, Self::Error> {
+        Box::pin(async move {
+            match self.resolve_vanity(vanity, vanity_type).await {
+                Ok(steamid) => Ok(Some(steamid)),
+                Err(WebApiError::NoMatch) => Ok(None),
+                Err(e) => Err(e),
+            }
+        })
+    }
+}
+
+impl SteamID {
+    /// Resolves an individual's vanity name (`steamcommunity.com/id/<vanity>`) with a one-off
+    /// [`WebApi`] client. Keep a `WebApi` around instead if you're resolving more than a few.
+    pub async fn resolve_vanity(api_key: &str, vanity: &str) -> Result<SteamID, WebApiError> {
+        WebApi::new(api_key)
+            .resolve_vanity(vanity, VanityType::Individual)
+            .await
+    }
+}
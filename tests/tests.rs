@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use steamid_ng::*;
 
 #[test]
@@ -42,6 +44,20 @@ fn test_manual_construction() {
     assert_eq!(s.universe(), Universe::Dev);
 }
 
+#[test]
+fn test_account_id_non_zero() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.account_id_non_zero().map(|id| id.get()), Some(22202));
+
+    let s = SteamID::new(
+        0,
+        Instance::All,
+        AccountType::AnonGameServer,
+        Universe::Public,
+    );
+    assert_eq!(s.account_id_non_zero(), None);
+}
+
 #[test]
 fn test_from_u64() {
     let s = SteamID::from(103582791432294076);
@@ -57,6 +73,34 @@ fn test_from_u64() {
     assert_eq!(s.universe(), Universe::Beta);
 }
 
+#[test]
+fn test_i64() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(i64::from(s), 76561197960287930);
+    assert_eq!(SteamID::try_from(76561197960287930i64), Ok(s));
+    assert_eq!(
+        SteamID::try_from(-1i64).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::InvalidUniverse)
+    );
+    assert_eq!(
+        SteamID::try_from(i64::from(SteamID::from(u64::MAX))).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::InvalidUniverse)
+    );
+    assert_eq!(i64::from(SteamID::from(u64::MAX)), -1);
+}
+
+#[test]
+fn test_steam32() {
+    let s = SteamID::from(76561197960287931);
+    assert_eq!(s.steam32(), Some(22203));
+    assert_eq!(SteamID::from_steam32(22203), s);
+    // The low bit is the auth server
+    assert_eq!(s.steam2(), "STEAM_1:1:11101");
+    assert_eq!(SteamID::from_steam32(22202).steam2(), "STEAM_1:0:11101");
+    assert_eq!(SteamID::from(103582791429521412).steam32(), None);
+    assert_eq!(SteamID::from_steam32(u32::MAX).account_id(), u32::MAX);
+}
+
 #[test]
 fn test_steam2() {
     let mut s = SteamID::from(76561197969249708);
@@ -70,6 +114,68 @@ fn test_steam2() {
     assert_eq!(s.steam2(), "157625991261918636");
 }
 
+#[test]
+fn test_try_steam2() {
+    let mut s = SteamID::from(76561197969249708);
+
+    assert_eq!(s.try_steam2().unwrap(), s.steam2());
+    s.set_account_type(AccountType::Invalid);
+    assert_eq!(s.try_steam2().unwrap(), "STEAM_1:0:4491990");
+    for account_type in [
+        AccountType::GameServer,
+        AccountType::Clan,
+        AccountType::Chat,
+    ] {
+        s.set_account_type(account_type);
+        let err = s.try_steam2().unwrap_err();
+        assert_eq!(err.account_type(), account_type);
+    }
+    assert_eq!(
+        s.try_steam2().unwrap_err().to_string(),
+        "Chat SteamIDs have no steam2 form"
+    );
+}
+
+#[test]
+fn test_steam2_with() {
+    let mut s = SteamID::from(76561197969249708);
+
+    assert_eq!(s.steam2_with(Steam2Format::GoldSrc), "STEAM_0:0:4491990");
+    assert_eq!(s.steam2_with(Steam2Format::OrangeBox), "STEAM_1:0:4491990");
+    s.set_universe(Universe::Beta);
+    assert_eq!(s.steam2_with(Steam2Format::GoldSrc), "STEAM_0:0:4491990");
+    assert_eq!(s.steam2_with(Steam2Format::OrangeBox), "STEAM_2:0:4491990");
+    s.set_account_type(AccountType::GameServer);
+    assert_eq!(s.steam2_with(Steam2Format::GoldSrc), s.steam2());
+
+    let mut buf = String::new();
+    s.set_account_type(AccountType::Individual);
+    s.write_steam2_with(Steam2Format::GoldSrc, &mut buf)
+        .unwrap();
+    assert_eq!(buf, "STEAM_0:0:4491990");
+}
+
+#[test]
+fn test_steam2_steamkit() {
+    let mut s = SteamID::from(76561197969249708);
+
+    assert_eq!(s.steam2_with(Steam2Format::SteamKit), s.steam2());
+    s.set_account_type(AccountType::Pending);
+    assert_eq!(s.steam2_with(Steam2Format::SteamKit), "STEAM_ID_PENDING");
+    s.set_account_type(AccountType::Invalid);
+    assert_eq!(s.steam2_with(Steam2Format::SteamKit), "UNKNOWN");
+    s.set_account_type(AccountType::Clan);
+    assert_eq!(s.steam2_with(Steam2Format::SteamKit), s.steam2());
+
+    for steamid in [
+        SteamID::from(0),
+        SteamID::from_steam2_extended("STEAM_ID_PENDING").unwrap(),
+    ] {
+        let rendered = steamid.steam2_with(Steam2Format::SteamKit);
+        assert_eq!(SteamID::from_steam2_extended(&rendered), Ok(steamid));
+    }
+}
+
 #[test]
 fn test_from_steam2() {
     let s = SteamID::from_steam2("STEAM_0:0:4491990").unwrap();
@@ -91,9 +197,61 @@ fn test_from_steam2() {
     assert_eq!(s.universe(), Universe::Public);
 
     assert_eq!(
-        SteamID::from_steam2("STEAM_bogus:bogus:bogus"),
-        Err(SteamIDParseError::default())
+        SteamID::from_steam2("STEAM_bogus:bogus:bogus").map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::UnexpectedCharacter)
+    );
+}
+
+#[test]
+fn test_from_steam2_extended() {
+    let s = SteamID::from_steam2_extended("STEAM_ID_PENDING").unwrap();
+    assert_eq!(s.account_type(), AccountType::Pending);
+    assert_eq!(s.universe(), Universe::Public);
+    assert_eq!(s.account_id(), 0);
+
+    assert_eq!(
+        SteamID::from_steam2_extended("STEAM_ID_LAN"),
+        Ok(SteamID::from(0))
+    );
+    assert_eq!(
+        SteamID::from_steam2_extended("UNKNOWN"),
+        Ok(SteamID::from(0))
+    );
+
+    let s = SteamID::from_steam2_extended("BOT").unwrap();
+    assert_eq!(s.account_type(), AccountType::AnonUser);
+    assert_eq!(s.universe(), Universe::Public);
+
+    assert_eq!(
+        SteamID::from_steam2_extended("STEAM_0:0:4491990"),
+        SteamID::from_steam2("STEAM_0:0:4491990")
+    );
+    assert!(SteamID::from_steam2("STEAM_ID_PENDING").is_err());
+    assert!(SteamID::from_steam2_extended("bot").is_err());
+}
+
+#[test]
+fn test_from_steam2_strict() {
+    assert_eq!(
+        SteamID::from_steam2_strict("STEAM_1:0:11101"),
+        Ok(SteamID::from(76561197960287930))
+    );
+    let err = SteamID::from_steam2_strict("STEAM_0:0:11101").unwrap_err();
+    assert_eq!(err.kind(), SteamIDParseErrorKind::UniverseZero);
+    assert_eq!(err.position(), Some(6));
+    assert_eq!(err.code(), 9);
+    assert_eq!(err.to_string(), "Malformed SteamID: universe 0 at byte 6");
+    assert_eq!(
+        SteamID::from_steam2_strict("STEAM_5:0:11101").map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::InvalidUniverse)
+    );
+
+    let strict = ParseOptions::new().reject_universe_zero(true);
+    assert_eq!(
+        SteamID::parse_with("STEAM_0:0:11101", strict).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::UniverseZero)
     );
+    assert!(SteamID::parse_with("STEAM_0:0:11101", ParseOptions::lenient()).is_ok());
 }
 
 #[test]
@@ -113,6 +271,19 @@ fn test_steam3_symmetric() {
     }
 }
 
+#[test]
+fn test_steam3_raw_instance_round_trip() {
+    for steam3 in ["[U:1:22202]", "[M:1:22202:3]"] {
+        let mut s = SteamID::from_steam3(steam3).unwrap();
+        s.set_instance_value(1337);
+        assert_eq!(s.instance(), Instance::Invalid);
+
+        let rendered = s.steam3();
+        assert!(rendered.ends_with(":1337]"), "{}", rendered);
+        assert_eq!(SteamID::from_steam3(&rendered).unwrap(), s);
+    }
+}
+
 #[test]
 fn test_from_steam3() {
     let s = SteamID::from_steam3("[U:1:123]").unwrap();
@@ -140,9 +311,103 @@ fn test_from_steam3() {
     assert_eq!(s.universe(), Universe::Internal);
 
     assert_eq!(
-        SteamID::from_steam3("[bogus:bogus:bogus]"),
-        Err(SteamIDParseError::default())
+        SteamID::from_steam3("[bogus:bogus:bogus]").map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::InvalidAccountType)
+    );
+}
+
+#[test]
+fn test_from_bytes() {
+    let gabe = SteamID::from(76561197960287930);
+    assert_eq!(SteamID::from_steam2_bytes(b"STEAM_1:0:11101"), Ok(gabe));
+    assert_eq!(SteamID::from_steam3_bytes(b"[U:1:22202]"), Ok(gabe));
+
+    // Errors are the same as for the str parsers
+    for input in [
+        "steam_1:0:11101",
+        "STEAM_1:0:",
+        "STEAM_1:0:x",
+        "STEAM_9:0:1",
+    ] {
+        let details = |e: SteamIDParseError| (e.kind(), e.position(), e.found());
+        assert_eq!(
+            SteamID::from_steam2_bytes(input.as_bytes()).map_err(details),
+            SteamID::from_steam2(input).map_err(details)
+        );
+    }
+
+    let err = SteamID::from_steam2_bytes(b"STEAM_1:0:\xff\xfe").unwrap_err();
+    assert_eq!(err.kind(), SteamIDParseErrorKind::UnexpectedCharacter);
+    assert_eq!(err.position(), Some(10));
+    assert_eq!(err.found(), Some(char::REPLACEMENT_CHARACTER));
+    let err = SteamID::from_steam3_bytes("[U:1:é]".as_bytes()).unwrap_err();
+    assert_eq!(err.position(), Some(5));
+    assert_eq!(err.found(), Some('é'));
+}
+
+#[test]
+fn test_steam3_bare() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.steam3_bare(), "U:1:22202");
+    assert_eq!(SteamID::from_steam3_bare("U:1:22202"), Ok(s));
+
+    let s = SteamID::new(
+        1234,
+        Instance::Web,
+        AccountType::AnonGameServer,
+        Universe::Public,
+    );
+    assert_eq!(s.steam3_bare(), "A:1:1234:4");
+    assert_eq!(SteamID::from_steam3_bare(&s.steam3_bare()), Ok(s));
+    assert_eq!(format!("[{}]", s.steam3_bare()), s.steam3());
+    let mut buf = String::new();
+    s.write_steam3_bare(&mut buf).unwrap();
+    assert_eq!(buf, "A:1:1234:4");
+
+    let kind = |s: &str| SteamID::from_steam3_bare(s).unwrap_err().kind();
+    assert_eq!(
+        kind("[U:1:22202]"),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+    assert_eq!(
+        kind("U:1:22202]"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+    assert_eq!(kind("U:1:"), SteamIDParseErrorKind::UnexpectedEnd);
+}
+
+#[test]
+fn test_write_into() {
+    use std::fmt::Write;
+
+    let s = SteamID::from(76561197960287930);
+    let mut out = String::from("id=");
+    s.write_steam2(&mut out).unwrap();
+    out.push(' ');
+    s.write_steam3(&mut out).unwrap();
+    let dynamic: &mut dyn Write = &mut out;
+    SteamID::from(103582791429521412)
+        .write_steam2(dynamic)
+        .unwrap();
+    assert_eq!(out, "id=STEAM_1:0:11101 [U:1:22202]103582791429521412");
+
+    let widest = SteamID::new(
+        u32::MAX,
+        Instance::FlagClan,
+        AccountType::AnonGameServer,
+        Universe::Dev,
     );
+    let mut buf = [0; SteamID::STEAM3_MAX_LEN];
+    let len = widest.render_steam3_into(&mut buf);
+    assert_eq!(&buf[..len], widest.steam3().as_bytes());
+    let len = s.render_steam3_into(&mut buf[..11]);
+    assert_eq!(&buf[..len], b"[U:1:22202]");
+}
+
+#[test]
+#[should_panic]
+fn test_render_steam3_into_short_buffer() {
+    SteamID::from(76561197960287930).render_steam3_into(&mut [0; 10]);
 }
 
 #[test]
@@ -154,7 +419,7 @@ fn test_serde() {
 
     let deserialized: SteamID = serde_json::from_str("\"STEAM_0:0:4491990\"").unwrap();
     // Prevent rustfmt bug where it adds an extra comma even though assert_eq! is a macro
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     assert_eq!(
         deserialized,
         SteamID::new(
@@ -166,7 +431,7 @@ fn test_serde() {
     );
 
     let deserialized: SteamID = serde_json::from_str("\"[U:1:123]\"").unwrap();
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     assert_eq!(
         deserialized,
         SteamID::new(
@@ -178,7 +443,7 @@ fn test_serde() {
     );
 
     let deserialized: SteamID = serde_json::from_str("103582791432294076").unwrap();
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     assert_eq!(
         deserialized,
         SteamID::new(2772668, Instance::All, AccountType::Clan, Universe::Public)
@@ -204,16 +469,3194 @@ fn test_serde() {
 }
 
 #[test]
-fn test_debug_print() {
-    let s = SteamID::from(157626004137848889);
-    let debug = format!("{:?}", s);
+fn test_display() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.to_string(), "76561197960287930");
+    assert_eq!(format!("{:#}", s), s.steam3());
+    assert_eq!(format!("{:.3}", s), s.steam3());
+    assert_eq!(format!("{:.2}", s), s.steam2());
+    assert_eq!(format!("{:.64}", s), "76561197960287930");
+    assert_eq!(format!("{:<20}|", s), "76561197960287930   |");
+    assert_eq!(format!("{:*^17.2}", s), "*STEAM_1:0:11101*");
+
+    let clan = SteamID::from(103582791429521412);
+    assert_eq!(format!("{:.2}", clan), "103582791429521412");
+    let widest = SteamID::new(
+        u32::MAX,
+        Instance::FlagClan,
+        AccountType::AnonGameServer,
+        Universe::Dev,
+    );
+    assert_eq!(format!("{:#}", widest), widest.steam3());
+    assert_eq!(SteamID::from(u64::MAX).to_string(), u64::MAX.to_string());
+}
+
+#[test]
+fn test_individual_id() {
+    use std::convert::TryFrom;
+
+    let s = SteamID::from(76561197960287930);
+    let id = IndividualID::try_from(s).unwrap();
+    assert_eq!(id, IndividualID::new(22202));
+    assert_eq!(SteamID::from(id), s);
+    assert_eq!(id.steamid(), s);
+    assert_eq!(id.account_id(), 22202);
+    assert_eq!(id.steam2(), "STEAM_1:0:11101");
+    assert_eq!(id.steam3(), "[U:1:22202]");
+    assert_eq!(format!("{:#}", id), "[U:1:22202]");
+
+    assert_eq!("[U:1:22202]".parse(), Ok(id));
     assert_eq!(
-        debug,
-        "SteamID(157626004137848889) {ID: 12345, Instance: Web, Type: GameServer, Universe: Beta}"
+        "[g:1:4]".parse::<IndividualID>().unwrap_err().kind(),
+        SteamIDParseErrorKind::InvalidAccountType
     );
+    assert_eq!(
+        IndividualID::try_from(SteamID::from(103582791429521412))
+            .unwrap_err()
+            .kind(),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+    assert!("STEAM1:0:11101".parse::<IndividualID>().is_err());
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "76561197960287930");
+    assert_eq!(
+        serde_json::from_str::<IndividualID>("\"[U:1:22202]\"").unwrap(),
+        id
+    );
+    assert!(serde_json::from_str::<IndividualID>("103582791429521412").is_err());
 }
 
 #[test]
-fn steam2_overflowing_account_id() {
-    let _ = SteamID::from_steam2("STEAM_0:0:9999999999");
+fn test_clan_id() {
+    use std::convert::TryFrom;
+
+    let s = SteamID::from(103582791429521412);
+    let id = ClanID::try_from(s).unwrap();
+    assert_eq!(id, ClanID::new(4));
+    assert_eq!(SteamID::from(id), s);
+    assert_eq!(id.account_id(), 4);
+    assert_eq!(id.steam3(), "[g:1:4]");
+    assert_eq!(
+        id.group_url(),
+        "https://steamcommunity.com/gid/103582791429521412"
+    );
+    assert_eq!(ClanID::from_group_url(&id.group_url()), Ok(id));
+    assert_eq!(
+        ClanID::from_group_url("https://steamcommunity.com/gid/76561197960287930")
+            .unwrap_err()
+            .kind(),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+
+    assert_eq!("[g:1:4]".parse(), Ok(id));
+    assert_eq!(
+        ClanID::try_from(SteamID::from(76561197960287930))
+            .unwrap_err()
+            .kind(),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "103582791429521412");
+    assert_eq!(serde_json::from_str::<ClanID>("\"[g:1:4]\"").unwrap(), id);
+    assert!(serde_json::from_str::<ClanID>("76561197960287930").is_err());
+}
+
+#[test]
+fn test_game_server_ids() {
+    use std::convert::TryFrom;
+
+    let id: GameServerID = "[G:1:626]".parse().unwrap();
+    assert_eq!(id, GameServerID::new(626));
+    assert_eq!(id.account_id(), 626);
+    assert_eq!(id.steam3(), "[G:1:626]");
+    assert!(GameServerID::try_from(SteamID::from_steam3("[A:1:626:1]").unwrap()).is_err());
+
+    let id: AnonGameServerID = "[A:1:165:5678]".parse().unwrap();
+    assert_eq!(id, AnonGameServerID::new(165, 5678).unwrap());
+    assert_eq!(id.account_id(), 165);
+    assert_eq!(id.instance_id(), 5678);
+    assert_eq!(id.steam3(), "[A:1:165:5678]");
+    assert_eq!(SteamID::from(id).steam3(), "[A:1:165:5678]");
+    assert_eq!(
+        AnonGameServerID::new(165, 0).unwrap().steam3(),
+        "[A:1:165:0]"
+    );
+    assert_eq!(
+        AnonGameServerID::new(165, AnonGameServerID::MAX_INSTANCE_ID)
+            .unwrap()
+            .instance_id(),
+        0xFFFFF
+    );
+    assert_eq!(AnonGameServerID::new(165, 0x100000), None);
+    assert_eq!(
+        "[G:1:165]".parse::<AnonGameServerID>().unwrap_err().kind(),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(serde_json::from_str::<AnonGameServerID>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<GameServerID>(&json).is_err());
+}
+
+#[test]
+fn test_lobby_id() {
+    use std::convert::TryFrom;
+
+    let id = LobbyID::from_lobby_index(100614564);
+    assert_eq!(u64::from(id), 109775241017770404);
+    assert_eq!(id.lobby_index(), 100614564);
+    assert!(id.is_mms_lobby());
+    assert_eq!(id.steam3(), "[L:1:100614564:393216]");
+    assert_eq!(id.steam3().parse(), Ok(id));
+    assert_eq!(
+        LobbyID::from_lobby_connect("+connect_lobby 109775241017770404"),
+        Ok(id)
+    );
+
+    let plain: LobbyID = "[L:1:100614564]".parse().unwrap();
+    assert!(!plain.is_mms_lobby());
+    assert_eq!(plain.lobby_index(), 100614564);
+
+    let kind = |s: &str| {
+        LobbyID::try_from(SteamID::from_steam3(s).unwrap())
+            .unwrap_err()
+            .kind()
+    };
+    assert_eq!(
+        kind("[T:1:100614564]"),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+    assert_eq!(
+        kind("[c:1:100614564]"),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+    assert_eq!(
+        kind("[U:1:100614564]"),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, "109775241017770404");
+    assert_eq!(serde_json::from_str::<LobbyID>(&json).unwrap(), id);
+}
+
+#[test]
+fn test_clan_chat() {
+    let clan = SteamID::from(103582791429521412);
+    let chat = clan.to_clan_chat().unwrap();
+    assert_eq!(chat.account_type(), AccountType::Chat);
+    assert_eq!(chat.instance(), Instance::FlagClan);
+    assert_eq!(chat.account_id(), clan.account_id());
+    assert_eq!(chat.universe(), clan.universe());
+    assert_eq!(chat.chat_to_clan(), Some(clan));
+
+    assert_eq!(SteamID::from(76561197960287930).to_clan_chat(), None);
+    assert_eq!(chat.to_clan_chat(), None);
+    assert_eq!(clan.chat_to_clan(), None);
+    assert_eq!(
+        SteamID::from_steam3("[T:1:4]").unwrap().chat_to_clan(),
+        None
+    );
+    assert_eq!(
+        SteamID::from_steam3("[L:1:4]").unwrap().chat_to_clan(),
+        None
+    );
+}
+
+#[test]
+fn test_chat_instance_flags() {
+    assert_eq!(SteamID::CHAT_INSTANCE_FLAG_CLAN, 0x80000);
+    assert_eq!(SteamID::CHAT_INSTANCE_FLAG_LOBBY, 0x40000);
+    assert_eq!(SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY, 0x20000);
+    assert_eq!(
+        SteamID::strip_chat_flags(0x80000 | 0x40000 | 0x20000 | 4),
+        4
+    );
+    assert_eq!(SteamID::strip_chat_flags(0x80000), 0);
+
+    let lobby = SteamID::from(109775241017770404);
+    assert_eq!(
+        lobby.chat_instance_flags(),
+        SteamID::CHAT_INSTANCE_FLAG_LOBBY | SteamID::CHAT_INSTANCE_FLAG_MMS_LOBBY
+    );
+    assert_eq!(
+        SteamID::from_steam3("[c:1:4]")
+            .unwrap()
+            .chat_instance_flags(),
+        SteamID::CHAT_INSTANCE_FLAG_CLAN
+    );
+    assert_eq!(SteamID::from(76561197960287930).chat_instance_flags(), 0);
+    assert_eq!(
+        u64::from(lobby) & u64::from(SteamID::ACCOUNT_ID_MASK),
+        u64::from(lobby.account_id())
+    );
+    assert_eq!(
+        (u64::from(lobby) >> 32) as u32 & SteamID::ACCOUNT_INSTANCE_MASK,
+        0x60000
+    );
+}
+
+#[test]
+fn test_steamid_parts() {
+    let s = SteamID::from_steam3("[A:2:165:4]").unwrap();
+    let parts = SteamIDParts::from(s);
+    assert_eq!(parts.account_id, 165);
+    assert_eq!(parts.instance, Instance::Web as u32);
+    assert_eq!(parts.account_type, AccountType::AnonGameServer);
+    assert_eq!(parts.universe, Universe::Beta);
+    assert_eq!(SteamID::try_from(parts), Ok(s));
+
+    match SteamIDParts::from(SteamID::from(76561197960287930)) {
+        SteamIDParts {
+            account_type: AccountType::Individual,
+            account_id,
+            ..
+        } => assert_eq!(account_id, 22202),
+        _ => panic!("Not an individual"),
+    }
+
+    let json = serde_json::to_string(&parts).unwrap();
+    assert_eq!(
+        json,
+        r#"{"account_id":165,"instance":4,"account_type":"AnonGameServer","universe":"Beta"}"#
+    );
+    assert_eq!(serde_json::from_str::<SteamIDParts>(&json).unwrap(), parts);
+
+    // Instances Instance has no variant for
+    for steam3 in ["[A:1:1234:5678]", "[L:1:100614564:393216]"] {
+        let s = SteamID::from_steam3(steam3).unwrap();
+        let parts = SteamIDParts::from(s);
+        assert_eq!(parts.instance, s.instance_value());
+        assert_eq!(SteamID::try_from(parts), Ok(s));
+        assert_eq!(SteamID::try_from(parts).unwrap().steam3(), steam3);
+    }
+
+    // Instances too wide for the field aren't truncated
+    let too_wide = SteamIDParts {
+        instance: SteamID::ACCOUNT_INSTANCE_MASK + 1,
+        ..parts
+    };
+    assert_eq!(
+        SteamID::try_from(too_wide).unwrap_err().kind(),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+    let widest = SteamIDParts {
+        instance: SteamID::ACCOUNT_INSTANCE_MASK,
+        ..parts
+    };
+    assert_eq!(
+        SteamID::try_from(widest).unwrap().instance_value(),
+        SteamID::ACCOUNT_INSTANCE_MASK
+    );
+}
+
+#[test]
+fn test_console_user() {
+    let psn = SteamID::from_psn_id(4_000_000_000).unwrap();
+    assert_eq!(psn.account_type(), AccountType::ConsoleUser);
+    assert_eq!(psn.instance(), Instance::Console);
+    assert_eq!(psn.universe(), Universe::Public);
+    assert_eq!(psn.account_id(), 4_000_000_000);
+    assert!(psn.is_console_user());
+    assert!(!SteamID::from(76561197960287930).is_console_user());
+
+    assert_eq!(SteamID::from_xbox_pairwise_id(4_000_000_000), Ok(psn));
+    assert_eq!(SteamID::from_switch_id(1), SteamID::from_psn_id(1));
+    assert_eq!(
+        SteamID::from_psn_id(u64::from(u32::MAX))
+            .unwrap()
+            .account_id(),
+        u32::MAX
+    );
+    // Ids that don't fit in the account id are refused rather than truncated
+    for too_wide in [1 << 32, u64::MAX] {
+        for err in [
+            SteamID::from_psn_id(too_wide),
+            SteamID::from_xbox_pairwise_id(too_wide),
+            SteamID::from_switch_id(too_wide),
+        ] {
+            assert_eq!(
+                err.unwrap_err().kind(),
+                SteamIDParseErrorKind::AccountIdOverflow
+            );
+        }
+    }
+
+    #[allow(deprecated)]
+    let old_name = AccountType::P2PSuperSeeder;
+    assert_eq!(old_name, AccountType::ConsoleUser);
+}
+
+#[test]
+fn test_anon_accounts() {
+    let user = SteamID::anon_user(Universe::Beta);
+    assert_eq!(user.account_type(), AccountType::AnonUser);
+    assert_eq!(user.universe(), Universe::Beta);
+    assert_eq!(user.account_id(), 0);
+    assert!(user.is_anonymous());
+    assert!(user.is_blank_anon_account());
+
+    let server = SteamID::anon_game_server(Universe::Public);
+    assert_eq!(server.steam3(), "[A:1:0:0]");
+    assert!(server.is_blank_anon_account());
+
+    let assigned = SteamID::from_steam3("[A:1:0:5678]").unwrap();
+    assert!(assigned.is_anonymous());
+    assert!(!assigned.is_blank_anon_account());
+    assert!(!SteamID::from_steam3("[A:1:165:0]")
+        .unwrap()
+        .is_blank_anon_account());
+    assert!(!SteamID::from_steam3("[U:1:0:0]").unwrap().is_anonymous());
+}
+
+#[test]
+fn test_layout_constants() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(u64::from(s) - SteamID::PUBLIC_INDIVIDUAL_BASE, 22202);
+    assert_eq!(
+        SteamID::from(SteamID::PUBLIC_INDIVIDUAL_BASE + 22202),
+        SteamID::from_steam32(22202)
+    );
+    assert_eq!(
+        SteamID::from(SteamID::PUBLIC_CLAN_BASE + 4),
+        SteamID::from_steam3("[g:1:4]").unwrap()
+    );
+    assert_eq!(
+        *SteamID::bounds_for_account_type(Universe::Public, AccountType::Individual).start(),
+        SteamID::from(SteamID::PUBLIC_INDIVIDUAL_BASE - (1 << SteamID::INSTANCE_SHIFT))
+    );
+
+    // The per-type ranges run from instance and account id 0 to every bit set
+    assert_eq!(
+        SteamID::PUBLIC_INDIVIDUAL_MIN,
+        SteamID::PUBLIC_INDIVIDUAL_BASE - (1 << SteamID::INSTANCE_SHIFT)
+    );
+    assert_eq!(
+        SteamID::PUBLIC_INDIVIDUAL_MAX + 1,
+        SteamID::PUBLIC_MULTISEAT_MIN
+    );
+    assert_eq!(
+        SteamID::PUBLIC_CLAN_MIN..=SteamID::PUBLIC_CLAN_MAX,
+        u64::from(*SteamID::bounds_for_account_type(Universe::Public, AccountType::Clan).start())
+            ..=u64::from(
+                *SteamID::bounds_for_account_type(Universe::Public, AccountType::Clan).end()
+            )
+    );
+    assert!((SteamID::PUBLIC_CLAN_MIN..=SteamID::PUBLIC_CLAN_MAX).contains(&103582791429521412));
+    assert_eq!(SteamID::PUBLIC_ANON_USER_MAX, (1 << 56) + (11 << 52) - 1);
+
+    let steam64 = u64::from(SteamID::from_steam3("[A:3:165:1234]").unwrap());
+    assert_eq!(steam64 as u32 & SteamID::ACCOUNT_ID_MASK, 165);
+    assert_eq!(
+        (steam64 >> SteamID::INSTANCE_SHIFT) as u32 & SteamID::ACCOUNT_INSTANCE_MASK,
+        1234
+    );
+    assert_eq!(
+        (steam64 >> SteamID::ACCOUNT_TYPE_SHIFT) as u32 & SteamID::ACCOUNT_TYPE_MASK,
+        AccountType::AnonGameServer as u32
+    );
+    assert_eq!(
+        (steam64 >> SteamID::UNIVERSE_SHIFT) as u32 & SteamID::UNIVERSE_MASK,
+        Universe::Internal as u32
+    );
+}
+
+#[test]
+fn test_is_valid() {
+    let valid = [
+        "[U:1:22202]",
+        "[U:1:22202:0]",
+        "[U:1:22202:4]",
+        "[g:1:4]",
+        "[G:1:626]",
+        "[A:1:0:0]",
+        "[a:1:0]",
+        "[L:1:100614564]",
+        "[T:4:1]",
+    ];
+    for id in valid {
+        assert!(SteamID::from_steam3(id).unwrap().is_valid(), "{}", id);
+    }
+
+    let invalid = [
+        "[U:1:0]",
+        "[U:1:22202:5]",
+        "[g:1:0]",
+        "[G:1:0]",
+        "[U:0:22202]",
+        "[I:1:22202]",
+    ];
+    for id in invalid {
+        assert!(!SteamID::from_steam3(id).unwrap().is_valid(), "{}", id);
+    }
+    assert!(!SteamID::from(0).is_valid());
+    let mut clan = SteamID::from_steam3("[g:1:4]").unwrap();
+    clan.set_instance(Instance::Desktop);
+    assert!(!clan.is_valid());
+    // An account type and universe Steam doesn't know
+    assert!(!SteamID::from(0x01F0_0000_0000_0001).is_valid());
+    assert!(!SteamID::from(0x0510_0001_0000_0001).is_valid());
+}
+
+#[test]
+fn test_account_kinds() {
+    let user = SteamID::from(76561197960287930);
+    assert!(user.is_individual());
+    assert!(!user.is_clan() && !user.is_chat() && !user.is_game_server());
+    assert!(!user.is_anonymous() && !user.is_console_user());
+
+    let clan = SteamID::from_steam3("[g:1:4]").unwrap();
+    assert!(clan.is_clan() && !clan.is_individual());
+    let clan_chat = clan.to_clan_chat().unwrap();
+    assert!(clan_chat.is_chat() && !clan_chat.is_lobby());
+
+    let lobby = SteamID::from_steam3("[L:1:100614564]").unwrap();
+    assert!(lobby.is_chat() && lobby.is_lobby());
+    let mut mms_lobby = lobby;
+    mms_lobby.set_instance(Instance::FlagMMSLobby);
+    assert!(!mms_lobby.is_lobby());
+
+    assert!(SteamID::from_steam3("[G:1:626]").unwrap().is_game_server());
+    let anon_server = SteamID::anon_game_server(Universe::Public);
+    assert!(anon_server.is_game_server() && anon_server.is_anonymous());
+    assert!(SteamID::from_psn_id(1234).unwrap().is_console_user());
+}
+
+#[test]
+fn test_raw_instance() {
+    assert_eq!(Instance::from_raw(4), Instance::Web);
+    assert_eq!(Instance::from_raw(0x80000), Instance::FlagClan);
+    assert_eq!(Instance::from_raw(3), Instance::Invalid);
+    assert_eq!(Instance::Console.raw(), 2);
+
+    let mut s = SteamID::from(76561197960287930);
+    s.set_instance_value(0xABCDE);
+    assert_eq!(s.instance_value(), 0xABCDE);
+    assert_eq!(s.account_id(), 22202);
+    assert_eq!(s.account_type(), AccountType::Individual);
+    assert_eq!(s.universe(), Universe::Public);
+
+    // Bits above the instance field are dropped, not spilled into the account type
+    s.set_instance_value(0xFFFF_FFFF);
+    assert_eq!(s.instance_value(), 0xFFFFF);
+    assert_eq!(s.account_type(), AccountType::Individual);
+}
+
+#[test]
+fn test_raw_account_type() {
+    let mut s = SteamID::from(76561197960287930);
+    assert_eq!(s.account_type_value(), 1);
+    s.set_account_type_value(11);
+    assert_eq!(s.account_type_value(), 11);
+    assert_eq!(s.account_type(), AccountType::Invalid);
+    assert_eq!(s.account_id(), 22202);
+    assert_eq!(s.instance(), Instance::Desktop);
+    assert_eq!(s.universe(), Universe::Public);
+    assert_eq!(SteamID::from_steam64(s.into()), Ok(s));
+
+    s.set_account_type_value(0xFF);
+    assert_eq!(s.account_type_value(), 15);
+    assert_eq!(s.universe(), Universe::Public);
+}
+
+#[test]
+fn test_raw_universe() {
+    let mut s = SteamID::from(76561197960287930);
+    assert_eq!(s.universe_value(), 1);
+    s.set_universe_value(0xA5);
+    assert_eq!(s.universe_value(), 0xA5);
+    assert_eq!(s.universe(), Universe::Invalid);
+    assert_eq!(s.account_id(), 22202);
+    assert_eq!(s.account_type(), AccountType::Individual);
+
+    assert_eq!(
+        SteamID::from_steam64(s.into()).unwrap_err().kind(),
+        SteamIDParseErrorKind::InvalidUniverse
+    );
+    assert_eq!(SteamID::from_steam64_any_universe(s.into()), Ok(s));
+    assert_eq!(
+        SteamID::from_steam64_any_universe(0xA510_0003_0000_56BA)
+            .unwrap_err()
+            .kind(),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+}
+
+#[test]
+fn test_try_new() {
+    use SteamIDParseErrorKind::*;
+
+    let err = |r: Result<SteamID, SteamIDParseError>| r.unwrap_err().kind();
+    assert_eq!(
+        SteamID::try_new(4, Instance::All, AccountType::Clan, Universe::Public),
+        Ok(SteamID::from(103582791429521412))
+    );
+    assert_eq!(
+        err(SteamID::try_new(
+            0,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public
+        )),
+        MissingAccountId
+    );
+    assert_eq!(
+        err(SteamID::try_new(
+            1,
+            Instance::FlagClan,
+            AccountType::Individual,
+            Universe::Public
+        )),
+        InvalidInstance
+    );
+    assert_eq!(
+        err(SteamID::try_new(
+            1,
+            Instance::All,
+            AccountType::Invalid,
+            Universe::Public
+        )),
+        InvalidAccountType
+    );
+    assert_eq!(
+        err(SteamID::try_new(
+            1,
+            Instance::All,
+            AccountType::GameServer,
+            Universe::Invalid
+        )),
+        InvalidUniverse
+    );
+    assert_eq!(
+        SteamID::try_new(0, Instance::All, AccountType::Clan, Universe::Public)
+            .unwrap_err()
+            .code(),
+        10
+    );
+
+    const GABEN: SteamID = SteamID::new_unchecked(
+        22202,
+        Instance::Desktop,
+        AccountType::Individual,
+        Universe::Public,
+    );
+    assert_eq!(GABEN, SteamID::from(76561197960287930));
+}
+
+#[test]
+fn test_steam2_account_id_overflow() {
+    let max = SteamID::from_steam2("STEAM_1:1:2147483647").unwrap();
+    assert_eq!(max.account_id(), u32::MAX);
+    assert_eq!(max.steam2(), "STEAM_1:1:2147483647");
+
+    for steam2 in ["STEAM_1:0:2147483648", "STEAM_1:1:4294967295"] {
+        let err = SteamID::from_steam2(steam2).unwrap_err();
+        assert_eq!(err.kind(), SteamIDParseErrorKind::AccountIdOverflow);
+        assert_eq!(err.position(), Some(10));
+    }
+}
+
+#[test]
+fn test_strict_rendering() {
+    for steam3 in [
+        "[U:1:22202]",
+        "[U:1:22202:4]",
+        "[M:1:22202:1]",
+        "[g:1:4]",
+        "[c:1:4]",
+        "[L:1:100614564]",
+        "[A:1:1234:5678]",
+    ] {
+        let s = SteamID::from_steam3(steam3).unwrap();
+        assert_eq!(s.steam3_strict().unwrap(), steam3);
+    }
+
+    // Clans never render their instance
+    let mut clan = SteamID::from(103582791429521412);
+    clan.set_instance(Instance::Desktop);
+    let err = clan.steam3_strict().unwrap_err();
+    assert_eq!(err.steamid(), clan);
+    assert_eq!(
+        err.to_string(),
+        "SteamID 103582795724488708 can't be written as steam3 without losing information"
+    );
+
+    let user = SteamID::from(76561197960287930);
+    assert_eq!(user.steam2_strict().unwrap(), "STEAM_1:0:11101");
+    let clan = SteamID::from(103582791429521412);
+    assert_eq!(clan.steam2_strict().unwrap_err().steamid(), clan);
+    assert!(SteamID::from_steam3("[U:1:22202:2]")
+        .unwrap()
+        .steam2_strict()
+        .is_err());
+}
+
+#[test]
+fn test_normalize() {
+    let user = SteamID::from(76561197960287930);
+    for instance in [Instance::All, Instance::Console, Instance::Web] {
+        let mut s = user;
+        s.set_instance(instance);
+        assert_eq!(s.normalize(), user);
+    }
+
+    let clan = SteamID::from(103582791429521412);
+    let mut s = clan;
+    s.set_instance_value(1);
+    assert_eq!(s.normalize(), clan);
+
+    let console = SteamID::from_psn_id(1234).unwrap();
+    let mut s = console;
+    s.set_instance(Instance::All);
+    assert_eq!(s.normalize(), console);
+
+    for steam3 in ["[L:1:100614564]", "[A:1:1234:5678]", "[M:1:22202:3]"] {
+        let s = SteamID::from_steam3(steam3).unwrap();
+        assert_eq!(s.normalize(), s);
+    }
+}
+
+#[test]
+fn test_eq_ignoring_instance() {
+    let user = SteamID::from(76561197960287930);
+    let web = SteamID::from_steam3("[U:1:22202:4]").unwrap();
+    let beta = SteamID::from_steam3("[U:2:22202]").unwrap();
+    let other = SteamID::from_steam3("[U:1:22203]").unwrap();
+    let clan = SteamID::from_steam3("[g:1:22202]").unwrap();
+
+    assert!(user.eq_ignoring_instance(&web));
+    assert!(!user.eq_ignoring_instance(&beta));
+    assert!(!user.eq_ignoring_instance(&other));
+    assert!(!user.eq_ignoring_instance(&clan));
+
+    assert!(web.eq_ignoring_instance_and_universe(&beta));
+    assert!(!user.eq_ignoring_instance_and_universe(&other));
+    assert!(!user.eq_ignoring_instance_and_universe(&clan));
+}
+
+#[test]
+fn test_validation_level() {
+    use ValidationLevel::*;
+
+    let kind = |r: Result<SteamID, SteamIDParseError>| r.unwrap_err().kind();
+    let web = 0x0110_0004_0000_56BA;
+    assert!(SteamID::from_steam64_with(web, BitfieldsOnly).is_ok());
+    assert!(SteamID::from_steam64_with(web, Semantic).is_ok());
+    assert_eq!(
+        kind(SteamID::from_steam64_with(web, Strict)),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+    assert_eq!(
+        kind(SteamID::from_steam64_with(0x0110_0001_0000_0000, Semantic)),
+        SteamIDParseErrorKind::MissingAccountId
+    );
+    assert_eq!(
+        kind(SteamID::from_steam64_with(
+            0x0510_0001_0000_56BA,
+            BitfieldsOnly
+        )),
+        SteamIDParseErrorKind::InvalidUniverse
+    );
+    assert_eq!(ValidationLevel::default(), BitfieldsOnly);
+
+    assert_eq!(
+        SteamID::try_new_with(
+            22202,
+            Instance::Web,
+            AccountType::Individual,
+            Universe::Public,
+            Semantic
+        ),
+        Ok(SteamID::from(web))
+    );
+    assert!(SteamID::try_new_with(
+        0,
+        Instance::Desktop,
+        AccountType::Individual,
+        Universe::Public,
+        BitfieldsOnly
+    )
+    .is_ok());
+
+    assert!(SteamID::parse_with("[U:1:0]", ParseOptions::new()).is_ok());
+    let options = ParseOptions::new().validation(Semantic);
+    assert_eq!(
+        kind(SteamID::parse_with("[U:1:0]", options)),
+        SteamIDParseErrorKind::MissingAccountId
+    );
+    assert_eq!(
+        kind(SteamID::parse_with("1", options)),
+        SteamIDParseErrorKind::InvalidAccountType
+    );
+    assert!(SteamID::parse_with("[U:1:22202]", options).is_ok());
+}
+
+#[test]
+fn test_nil() {
+    assert_eq!(SteamID::nil(), SteamID::default());
+    assert_eq!(u64::from(SteamID::nil()), 0);
+    assert!(SteamID::nil().is_nil());
+    assert!(!SteamID::nil().is_valid());
+    assert!(!SteamID::from(76561197960287930).is_nil());
+    assert!(!SteamID::from_steam3("[I:0:0:1]").unwrap().is_nil());
+}
+
+#[test]
+fn test_try_set_raw() {
+    use SteamIDParseErrorKind::*;
+
+    let gabe = SteamID::from(76561197960287930);
+    let mut s = gabe;
+    assert_eq!(
+        s.try_set_account_type(16).unwrap_err().kind(),
+        InvalidAccountType
+    );
+    assert_eq!(s.try_set_universe(5).unwrap_err().kind(), InvalidUniverse);
+    assert_eq!(s.try_set_instance(3).unwrap_err().kind(), InvalidInstance);
+    assert_eq!(
+        s.try_set_instance(0x100001).unwrap_err().kind(),
+        InvalidInstance
+    );
+    assert_eq!(s, gabe);
+
+    s.try_set_account_type(7).unwrap();
+    assert_eq!(s.account_type(), AccountType::Clan);
+    s.try_set_account_type(12).unwrap();
+    assert_eq!(s.account_type_value(), 12);
+    s.try_set_universe(4).unwrap();
+    assert_eq!(s.universe(), Universe::Dev);
+    // Only chats carry the chat flags
+    assert_eq!(
+        s.try_set_instance(0x60001).unwrap_err().kind(),
+        InvalidInstance
+    );
+    s.try_set_account_type(8).unwrap();
+    s.try_set_instance(0x60001).unwrap();
+    assert_eq!(s.instance_value(), 0x60001);
+    assert_eq!(s.account_id(), 22202);
+    assert_eq!(SteamID::from_steam64(s.into()), Ok(s));
+}
+
+#[test]
+fn test_instance_flags() {
+    let lobby = SteamID::from_steam3("[T:1:100614564:393216]").unwrap();
+    let flags = lobby.instance_flags();
+    assert_eq!(flags, InstanceFlags::LOBBY | InstanceFlags::MMS_LOBBY);
+    assert!(flags.contains(InstanceFlags::MMS_LOBBY));
+    assert!(!flags.intersects(InstanceFlags::CLAN | InstanceFlags::DESKTOP));
+
+    let user = SteamID::from_steam3("[U:1:22202]").unwrap();
+    assert_eq!(user.instance_flags(), InstanceFlags::DESKTOP);
+    let mut s = user;
+    s.set_instance_value(InstanceFlags::DESKTOP.union(InstanceFlags::WEB).bits());
+    assert_eq!(s.instance(), Instance::Invalid);
+    assert!(s.instance_flags().contains(InstanceFlags::WEB));
+
+    assert_eq!(Instance::FlagLobby.flags(), InstanceFlags::LOBBY);
+    assert!(Instance::All.flags().is_empty());
+    assert!(Instance::Invalid.flags().is_empty());
+    assert_eq!(InstanceFlags::from_bits(0x1234), None);
+    assert_eq!(
+        InstanceFlags::from_bits_truncate(0x1235),
+        InstanceFlags::DESKTOP | InstanceFlags::WEB
+    );
+
+    let json = serde_json::to_string(&flags).unwrap();
+    assert_eq!(json, "393216");
+    assert_eq!(serde_json::from_str::<InstanceFlags>(&json).unwrap(), flags);
+    assert!(serde_json::from_str::<InstanceFlags>("8").is_err());
+}
+
+#[test]
+fn test_steam3_chat_instance() {
+    let mms_lobby = SteamID::from(109775241017770404);
+    assert_eq!(mms_lobby.steam3(), "[L:1:100614564:393216]");
+    assert_eq!(SteamID::from_steam3(&mms_lobby.steam3()), Ok(mms_lobby));
+
+    // A chat with only the matchmaking flag has no type char of its own
+    let mut mms_only = mms_lobby;
+    mms_only.set_instance(Instance::FlagMMSLobby);
+    assert_eq!(mms_only.steam3(), "[T:1:100614564:131072]");
+    assert_eq!(SteamID::from_steam3(&mms_only.steam3()), Ok(mms_only));
+
+    // The type char's flag is kept even if the instance leaves it out
+    let lobby = SteamID::from_steam3("[L:1:100614564:131072]").unwrap();
+    assert_eq!(lobby, mms_lobby);
+
+    for steam3 in ["[L:1:100614564]", "[c:1:4]", "[T:1:4]"] {
+        assert_eq!(SteamID::from_steam3(steam3).unwrap().steam3(), steam3);
+    }
+}
+
+#[test]
+fn test_builder() {
+    assert_eq!(
+        SteamID::builder().account_id(22202).build(),
+        Ok(SteamID::from(76561197960287930))
+    );
+    assert_eq!(
+        SteamID::builder()
+            .account_id(22202)
+            .instance(Instance::Web)
+            .universe(Universe::Beta)
+            .build()
+            .unwrap()
+            .steam3(),
+        "[U:2:22202:4]"
+    );
+    assert_eq!(
+        SteamID::builder()
+            .account_id(1234)
+            .account_type(AccountType::ConsoleUser)
+            .build(),
+        Ok(SteamID::from_psn_id(1234).unwrap())
+    );
+    assert_eq!(
+        SteamID::builder()
+            .account_id(4)
+            .account_type(AccountType::Clan)
+            .build()
+            .unwrap()
+            .instance(),
+        Instance::All
+    );
+
+    let err = |builder: SteamIDBuilder| builder.build().unwrap_err().kind();
+    assert_eq!(
+        err(SteamID::builder()),
+        SteamIDParseErrorKind::MissingAccountId
+    );
+    assert_eq!(
+        err(SteamID::builder()
+            .account_id(4)
+            .account_type(AccountType::Clan)
+            .instance(Instance::Desktop)),
+        SteamIDParseErrorKind::InvalidInstance
+    );
+}
+
+#[test]
+fn test_with_setters() {
+    let gabe = SteamID::from(76561197960287930);
+    let clan = gabe
+        .with_account_id(4)
+        .with_account_type(AccountType::Clan)
+        .with_instance(Instance::All);
+    assert_eq!(clan, SteamID::from(103582791429521412));
+    assert_eq!(gabe.steam3(), "[U:1:22202]");
+
+    let ids: Vec<SteamID> = (1..=3).map(|id| gabe.with_account_id(id)).collect();
+    assert_eq!(ids[2].steam3(), "[U:1:3]");
+
+    let mut set = gabe;
+    set.set_universe(Universe::Dev);
+    assert_eq!(gabe.with_universe(Universe::Dev), set);
+}
+
+#[test]
+fn test_account_type_from_str() {
+    for account_type in [
+        AccountType::Invalid,
+        AccountType::Individual,
+        AccountType::Multiseat,
+        AccountType::GameServer,
+        AccountType::AnonGameServer,
+        AccountType::Pending,
+        AccountType::ContentServer,
+        AccountType::Clan,
+        AccountType::Chat,
+        AccountType::ConsoleUser,
+        AccountType::AnonUser,
+    ] {
+        assert_eq!(account_type.to_string().parse(), Ok(account_type));
+        assert_eq!(
+            account_type.to_string().to_uppercase().parse(),
+            Ok(account_type)
+        );
+    }
+
+    assert_eq!("U".parse(), Ok(AccountType::Individual));
+    assert_eq!("C".parse(), Ok(AccountType::ContentServer));
+    assert_eq!("c".parse(), Ok(AccountType::Chat));
+    assert_eq!("a".parse(), Ok(AccountType::AnonUser));
+    assert_eq!("i".parse(), Ok(AccountType::ConsoleUser));
+    assert_eq!("I".parse(), Ok(AccountType::Invalid));
+
+    let err = "X".parse::<AccountType>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown account type");
+    assert!("".parse::<AccountType>().is_err());
+    assert!("Clans".parse::<AccountType>().is_err());
+}
+
+#[test]
+fn test_universe_from_str() {
+    for universe in [
+        Universe::Invalid,
+        Universe::Public,
+        Universe::Beta,
+        Universe::Internal,
+        Universe::Dev,
+    ] {
+        assert_eq!(universe.to_string().parse(), Ok(universe));
+        assert_eq!(universe.to_string().to_lowercase().parse(), Ok(universe));
+    }
+
+    let err = "1".parse::<Universe>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown universe");
+    assert!("publics".parse::<Universe>().is_err());
+}
+
+#[test]
+fn test_instance_from_str() {
+    for instance in [
+        Instance::All,
+        Instance::Desktop,
+        Instance::Console,
+        Instance::Web,
+        Instance::Invalid,
+        Instance::FlagClan,
+        Instance::FlagLobby,
+        Instance::FlagMMSLobby,
+    ] {
+        assert_eq!(instance.to_string().parse(), Ok(instance));
+        assert_eq!(instance.to_string().to_lowercase().parse(), Ok(instance));
+        assert_eq!(instance.raw().to_string().parse(), Ok(instance));
+    }
+
+    assert_eq!("524288".parse(), Ok(Instance::FlagClan));
+    let err = "3".parse::<Instance>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown instance");
+    assert!("-1".parse::<Instance>().is_err());
+    assert!("Mobile".parse::<Instance>().is_err());
+}
+
+#[test]
+fn test_redacted() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.redacted().to_string(), "7656119796028****");
+    assert_eq!(s.redacted().keep(0).to_string(), "*****************");
+    assert_eq!(s.redacted().keep(100).to_string(), "76561197960287930");
+    assert_eq!(format!("{:>19}", s.redacted()), "  7656119796028****");
+
+    assert_eq!(SteamID::from(12345).redacted().to_string(), "12345");
+    assert_eq!(SteamID::from(12345).redacted().keep(2).to_string(), "12***");
+}
+
+#[test]
+fn test_enum_display() {
+    assert_eq!(AccountType::Individual.to_string(), "Individual");
+    assert_eq!(AccountType::AnonGameServer.to_string(), "AnonGameServer");
+    assert_eq!(Universe::Public.to_string(), "Public");
+    assert_eq!(Instance::Desktop.to_string(), "Desktop");
+    assert_eq!(Instance::FlagLobby.to_string(), "FlagLobby");
+    assert_eq!(format!("{:<8}|", Universe::Dev), "Dev     |");
+    assert_eq!(format!("{:>8}", Instance::Web), "     Web");
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.steam2_array(), s.steam2().as_str());
+    assert_eq!(s.steam3_array(), s.steam3().as_str());
+
+    // The longest of each
+    let s = SteamID::new(
+        u32::MAX,
+        Instance::FlagClan,
+        AccountType::AnonGameServer,
+        Universe::Dev,
+    );
+    assert_eq!(s.steam3_array(), "[A:4:4294967295:524288]");
+    assert_eq!(
+        SteamID::from(u64::MAX).steam2_array().len(),
+        SteamID::STEAM2_MAX_LEN
+    );
+    let mut s = SteamID::from(u64::MAX);
+    s.set_account_type(AccountType::Individual);
+    assert_eq!(s.steam2_array(), s.steam2().as_str());
+}
+
+#[test]
+fn test_radix_fmt() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(format!("{:x}", s), "1100001000056ba");
+    assert_eq!(format!("{:#X}", s), "0x1100001000056BA");
+    assert_eq!(format!("{:#018x}", s), "0x01100001000056ba");
+    assert_eq!(format!("{:b}", s), format!("{:b}", u64::from(s)));
+
+    assert_eq!(format!("{:#x}", Instance::FlagClan), "0x80000");
+    assert_eq!(format!("{:X}", Instance::Invalid), "29A");
+    assert_eq!(format!("{:#b}", Instance::Web), "0b100");
+}
+
+#[test]
+fn test_formatter() {
+    let s = SteamID::from(76561197960287930);
+    let formatter: SteamIDFormatter =
+        "{{{account_type_name}}} {steam2} = {steam3} in {universe_name} ({universe}/{account_type}/{instance})"
+            .parse()
+            .unwrap();
+    assert_eq!(
+        formatter.format(s),
+        "{Individual} STEAM_1:0:11101 = [U:1:22202] in Public (1/1/1)"
+    );
+
+    for (preset, rendered) in [
+        ("steam64", "76561197960287930"),
+        ("steam2", "STEAM_1:0:11101"),
+        ("steam3", "[U:1:22202]"),
+        ("steam3_bare", "U:1:22202"),
+        (
+            "community_url",
+            "https://steamcommunity.com/profiles/76561197960287930",
+        ),
+    ] {
+        assert_eq!(
+            SteamIDFormatter::preset(preset).unwrap().format(s),
+            rendered
+        );
+    }
+    assert_eq!(SteamIDFormatter::preset("nope"), None);
+
+    // The instance is raw, and steam3_bare matches SteamID::steam3_bare
+    for steam3 in ["[U:1:22202:4]", "[A:1:1234:5678]"] {
+        let s = SteamID::from_steam3(steam3).unwrap();
+        assert_eq!(
+            SteamIDFormatter::preset("steam3_bare").unwrap().format(s),
+            s.steam3_bare()
+        );
+    }
+    let anon = SteamID::from_steam3("[A:1:1234:5678]").unwrap();
+    assert_eq!(
+        SteamIDFormatter::new("{instance}").unwrap().format(anon),
+        "5678"
+    );
+    // So are the account type and universe, even ones with no enum variant
+    let mut unknown = s;
+    unknown.set_account_type_value(13);
+    unknown.set_universe_value(200);
+    assert_eq!(
+        SteamIDFormatter::new("{universe}/{account_type}")
+            .unwrap()
+            .format(unknown),
+        "200/13"
+    );
+    assert_eq!(SteamIDFormatter::new("").unwrap().format(s), "");
+    assert_eq!(SteamIDFormatter::new("plain").unwrap().format(s), "plain");
+
+    let mut out = String::from("> ");
+    SteamIDFormatter::new("{type_char}{account_id}")
+        .unwrap()
+        .write(s, &mut out)
+        .unwrap();
+    assert_eq!(out, "> U22202");
+
+    let position = |template: &str| SteamIDFormatter::new(template).unwrap_err().position();
+    assert_eq!(position("id: {steam4}"), 4);
+    assert_eq!(position("id: {steam64"), 4);
+    assert_eq!(position("id} {steam64}"), 2);
+    assert_eq!(
+        SteamIDFormatter::new("{").unwrap_err().to_string(),
+        "Malformed SteamID template at byte 0"
+    );
+}
+
+#[test]
+fn test_debug_print() {
+    let s = SteamID::from(157626004137848889);
+    let debug = format!("{:?}", s);
+    assert_eq!(
+        debug,
+        "SteamID(157626004137848889) {ID: 12345, Instance: Web, Type: GameServer, Universe: Beta}"
+    );
+}
+
+#[test]
+fn steam2_overflowing_account_id() {
+    let _ = SteamID::from_steam2("STEAM_0:0:9999999999");
+}
+
+#[test]
+fn test_account_id() {
+    let s = SteamID::from(76561197960287930);
+    let id = s.account_id_typed();
+    assert_eq!(id, AccountID::from(22202));
+    assert_eq!(u32::from(id), s.account_id());
+    assert_eq!(SteamID::from(id), s);
+    assert_eq!("22202".parse(), Ok(id));
+    assert!("4294967296".parse::<AccountID>().is_err());
+    assert!("-1".parse::<AccountID>().is_err());
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "22202");
+    assert_eq!(serde_json::from_str::<AccountID>("\"22202\"").unwrap(), id);
+    assert!(serde_json::from_str::<AccountID>("76561197960287930").is_err());
+}
+
+#[test]
+fn test_game_id() {
+    let tf2 = GameID::from_app_id(440);
+    assert_eq!(u64::from(tf2), 440);
+    assert_eq!(tf2.app_id(), 440);
+    assert_eq!(tf2.game_type(), Some(GameIDType::App));
+    assert_eq!(tf2.mod_id(), 0);
+    assert!(tf2.is_steam_app());
+    assert_eq!(GameID::from_app_id(0x1FF_FFFF).app_id(), 0xFF_FFFF);
+
+    let cstrike = GameID::from_mod(240, "cstrike");
+    assert_eq!(u64::from(cstrike), 11184425126080807152);
+    assert_eq!(cstrike.app_id(), 240);
+    assert_eq!(cstrike.mod_id(), 0x9b370f65);
+    assert!(cstrike.is_mod());
+
+    let shortcut = GameID::from_shortcut("\"C:\\Games\\game.exe\"", "My Game");
+    assert_eq!(u64::from(shortcut), 12962102314566418432);
+    assert_eq!(shortcut.mod_id(), 3017974625);
+    assert!(shortcut.is_shortcut());
+    assert!(!shortcut.is_steam_app());
+
+    let p2p = GameID::new(440, GameIDType::P2P, 7);
+    assert!(p2p.is_p2p_file());
+    assert_eq!(p2p.mod_id(), 7);
+    assert_eq!(GameID::from(0x0900_0000).game_type(), None);
+
+    assert_eq!(shortcut.to_string(), "12962102314566418432");
+    assert_eq!("12962102314566418432".parse(), Ok(shortcut));
+    assert_eq!(
+        serde_json::from_str::<GameID>("\"12962102314566418432\"").unwrap(),
+        shortcut
+    );
+    assert_eq!(serde_json::to_string(&tf2).unwrap(), "440");
+}
+
+#[test]
+fn test_global_id() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let gid = GlobalID::new(1023, 15, 0x3FFF_FFFF, 0xF_FFFF);
+    assert_eq!(u64::from(gid), u64::MAX);
+    assert!(gid.is_nil());
+
+    let gid = GlobalID::new(12, 3, 86400, 5);
+    assert_eq!(gid.box_id(), 12);
+    assert_eq!(gid.process_id(), 3);
+    assert_eq!(gid.start_time_seconds(), 86400);
+    assert_eq!(
+        gid.start_time(),
+        UNIX_EPOCH + Duration::from_secs(1_104_537_600 + 86400)
+    );
+    assert_eq!(gid.sequence_count(), 5);
+    assert_eq!(u64::from(gid), 12 << 54 | 3 << 50 | 86400 << 20 | 5);
+    assert!(!gid.is_nil());
+
+    // Bits that don't fit are dropped, rather than spilling into the next field
+    let gid = GlobalID::new(1024, 16, 0x4000_0000, 0x10_0000);
+    assert_eq!(u64::from(gid), 0);
+
+    let job: JobID = "18446744073709551615".parse().unwrap();
+    assert_eq!(job, GlobalID::NIL);
+    assert_eq!(serde_json::to_string(&job).unwrap(), "18446744073709551615");
+}
+
+#[test]
+fn test_app_and_depot_ids() {
+    let tf2 = AppID::from(440);
+    assert_eq!(AppID::from_url(&tf2.store_url()), Some(tf2));
+    for url in [
+        "https://store.steampowered.com/app/440/Team_Fortress_2/",
+        "store.steampowered.com/app/440",
+        "http://store.steampowered.com/app/440?snr=1_7_7_230_150_1",
+        "https://steamdb.info/app/440/depots/",
+        "https://www.steamdb.info/app/440#info",
+    ] {
+        assert_eq!(AppID::from_url(url), Some(tf2), "{}", url);
+    }
+    for url in [
+        "https://store.steampowered.com/app/",
+        "https://store.steampowered.com/app/4294967296/",
+        "https://store.steampowered.com/sub/440/",
+        "https://store.steampowered.com/app/+440/",
+        "https://example.com/app/440/",
+    ] {
+        assert_eq!(AppID::from_url(url), None, "{}", url);
+    }
+    assert_eq!("440".parse(), Ok(tf2));
+    assert_eq!(serde_json::from_str::<AppID>("\"440\"").unwrap(), tf2);
+
+    let depot = DepotID::from(441);
+    assert_eq!(
+        DepotID::from_url("https://steamdb.info/depot/441/"),
+        Some(depot)
+    );
+    assert_eq!(DepotID::from_url("https://steamdb.info/app/441/"), None);
+    assert_eq!(depot.to_string(), "441");
+    assert_eq!(serde_json::to_string(&depot).unwrap(), "441");
+}
+
+#[test]
+fn test_package_id() {
+    let id = PackageID::from(469);
+    assert_eq!(id.store_url(), "https://store.steampowered.com/sub/469/");
+    assert_eq!(PackageID::from_url(&id.store_url()), Some(id));
+    assert_eq!(
+        PackageID::from_url("https://steamdb.info/sub/469/apps/"),
+        Some(id)
+    );
+    assert_eq!(
+        PackageID::from_url("https://store.steampowered.com/app/469/"),
+        None
+    );
+
+    assert_eq!("469".parse(), Ok(id));
+    assert!("sub469".parse::<PackageID>().is_err());
+    assert_eq!(id.to_string(), "469");
+    assert_eq!(serde_json::to_string(&id).unwrap(), "469");
+    assert_eq!(serde_json::from_str::<PackageID>("\"469\"").unwrap(), id);
+}
+
+#[test]
+fn test_bundle_id() {
+    let id: BundleID = "232".parse().unwrap();
+    assert_eq!(u32::from(id), 232);
+    assert_eq!(id.to_string(), "232");
+    assert!("bogus".parse::<BundleID>().is_err());
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "232");
+    assert_eq!(serde_json::from_str::<BundleID>("232").unwrap(), id);
+    assert_eq!(serde_json::from_str::<BundleID>("\"232\"").unwrap(), id);
+    assert!(serde_json::from_str::<BundleID>("4294967296").is_err());
+}
+
+#[test]
+fn test_party_beacon_id() {
+    let id = PartyBeaconID::from(123456789012345678);
+    assert_eq!(u64::from(id), 123456789012345678);
+    assert_eq!("123456789012345678".parse(), Ok(id));
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "123456789012345678");
+    assert_eq!(
+        serde_json::from_str::<PartyBeaconID>("\"123456789012345678\"").unwrap(),
+        id
+    );
+}
+
+#[test]
+fn test_published_file_id() {
+    let id = PublishedFileID::from(2871108184);
+    assert_eq!(
+        PublishedFileID::from_workshop_url(&id.workshop_url()),
+        Some(id)
+    );
+    for url in [
+        "steamcommunity.com/sharedfiles/filedetails/?id=2871108184",
+        "http://www.steamcommunity.com/sharedfiles/filedetails?id=2871108184",
+        "https://steamcommunity.com/workshop/filedetails/?searchtext=map&id=2871108184#comments",
+    ] {
+        assert_eq!(PublishedFileID::from_workshop_url(url), Some(id), "{}", url);
+    }
+    for url in [
+        "https://steamcommunity.com/sharedfiles/filedetails/?id=",
+        "https://steamcommunity.com/sharedfiles/filedetails/?id=+2871108184",
+        "https://steamcommunity.com/sharedfiles/filedetails/?appid=2871108184",
+        "https://steamcommunity.com/profiles/?id=2871108184",
+        "https://example.com/sharedfiles/filedetails/?id=2871108184",
+    ] {
+        assert_eq!(PublishedFileID::from_workshop_url(url), None, "{}", url);
+    }
+
+    assert_eq!(
+        serde_json::from_str::<PublishedFileID>("\"2871108184\"").unwrap(),
+        id
+    );
+    let ugc: UGCID = "1234567890123456789".parse().unwrap();
+    assert_eq!(u64::from(ugc), 1234567890123456789);
+    assert_eq!(serde_json::to_string(&ugc).unwrap(), "1234567890123456789");
+}
+
+#[test]
+fn test_from_lobby_connect() {
+    let lobby = SteamID::from(109775241017770404);
+    assert_eq!(lobby.account_type(), AccountType::Chat);
+
+    assert_eq!(
+        SteamID::from_lobby_connect("+connect_lobby 109775241017770404"),
+        Ok(lobby)
+    );
+    assert_eq!(
+        SteamID::from_lobby_connect("+map koth_viaduct +connect_lobby 109775241017770404 -novid"),
+        Ok(lobby)
+    );
+    assert_eq!(
+        SteamID::from_lobby_connect("steam://joinlobby/440/109775241017770404/76561197960287930"),
+        Ok(lobby)
+    );
+    assert_eq!(
+        SteamID::from_lobby_connect("steam://joinlobby/440/109775241017770404"),
+        Ok(lobby)
+    );
+
+    // Not a lobby
+    assert!(SteamID::from_lobby_connect("+connect_lobby 76561197960287930").is_err());
+    assert!(SteamID::from_lobby_connect("+connect_lobby +109775241017770404").is_err());
+    assert!(SteamID::from_lobby_connect("+connect_lobby").is_err());
+    assert!(SteamID::from_lobby_connect("steam://joinlobby/bogus/109775241017770404").is_err());
+}
+
+#[test]
+fn test_serde_empty_string_as_none() {
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Player {
+        #[serde(default, with = "steamid_ng::serde_helpers::empty_string_as_none")]
+        clan: Option<SteamID>,
+    }
+
+    let none = Player { clan: None };
+    let some = Player {
+        clan: Some(SteamID::from(103582791432294076)),
+    };
+
+    assert_eq!(
+        serde_json::from_str::<Player>(r#"{"clan": ""}"#).unwrap(),
+        none
+    );
+    assert_eq!(
+        serde_json::from_str::<Player>(r#"{"clan": null}"#).unwrap(),
+        none
+    );
+    assert_eq!(serde_json::from_str::<Player>("{}").unwrap(), none);
+    assert_eq!(
+        serde_json::from_str::<Player>(r#"{"clan": "103582791432294076"}"#).unwrap(),
+        some
+    );
+    assert_eq!(
+        serde_json::from_str::<Player>(r#"{"clan": 103582791432294076}"#).unwrap(),
+        some
+    );
+    assert!(serde_json::from_str::<Player>(r#"{"clan": "bogus"}"#).is_err());
+
+    assert_eq!(serde_json::to_string(&none).unwrap(), r#"{"clan":""}"#);
+    assert_eq!(
+        serde_json::to_string(&some).unwrap(),
+        r#"{"clan":103582791432294076}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_try_from_json_value() {
+    use serde_json::json;
+    use std::convert::TryFrom;
+
+    let expected = SteamID::from(76561197960287930);
+    assert_eq!(
+        SteamID::try_from(&json!(76561197960287930u64)),
+        Ok(expected)
+    );
+    assert_eq!(SteamID::try_from(&json!("76561197960287930")), Ok(expected));
+    assert_eq!(SteamID::try_from(&json!("[U:1:22202]")), Ok(expected));
+    assert_eq!(SteamID::try_from(&json!(22202.0)), Ok(SteamID::from(22202)));
+
+    assert!(SteamID::try_from(&json!(7.656119796028793e16)).is_err());
+    assert!(SteamID::try_from(&json!(1.5)).is_err());
+    assert!(SteamID::try_from(&json!(-1)).is_err());
+    assert!(SteamID::try_from(&json!(null)).is_err());
+    assert!(SteamID::try_from(&json!("bogus")).is_err());
+}
+
+#[test]
+fn test_estimated_signup_cohort() {
+    use steamid_ng::cohort::SignupCohort;
+
+    assert_eq!(
+        SteamID::from(76561197960287930).estimated_signup_cohort(),
+        Some(2003)
+    );
+    assert_eq!(
+        SteamID::from_steam3("[U:1:1200000000]")
+            .unwrap()
+            .estimated_signup_cohort(),
+        Some(2021)
+    );
+    assert_eq!(
+        SteamID::from(103582791432294076).estimated_signup_cohort(),
+        None
+    );
+    assert_eq!(
+        SteamID::new(
+            0,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public
+        )
+        .estimated_signup_cohort(),
+        None
+    );
+
+    let table = [
+        SignupCohort {
+            first_account_id: 100,
+            year: 2010,
+        },
+        SignupCohort {
+            first_account_id: 200,
+            year: 2020,
+        },
+    ];
+    let id = |account_id| {
+        SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        )
+    };
+    assert_eq!(id(99).estimated_signup_cohort_with(&table), None);
+    assert_eq!(id(100).estimated_signup_cohort_with(&table), Some(2010));
+    assert_eq!(id(250).estimated_signup_cohort_with(&table), Some(2020));
+}
+
+#[cfg(feature = "surrealdb")]
+#[test]
+fn test_surrealdb_value() {
+    use surrealdb_types::{Number, SurrealValue, Value};
+
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(
+        s.into_value(),
+        Value::Number(Number::Int(76561197960287930))
+    );
+    assert_eq!(
+        s.to_surreal_value(SurrealRepr::String),
+        Value::String("76561197960287930".to_owned())
+    );
+
+    assert_eq!(SteamID::from_value(s.into_value()).unwrap(), s);
+    assert_eq!(
+        SteamID::from_value(s.to_surreal_value(SurrealRepr::String)).unwrap(),
+        s
+    );
+    assert_eq!(
+        SteamID::from_value(Value::String("[U:1:22202]".to_owned())).unwrap(),
+        s
+    );
+    assert!(SteamID::from_value(Value::String("bogus".to_owned())).is_err());
+    assert!(SteamID::from_value(Value::Bool(true)).is_err());
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi() {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use steamid_ng::ffi::*;
+
+    assert_eq!(
+        convert("STEAM_1:0:11101", OutputFormat::Steam3),
+        Ok("[U:1:22202]".to_owned())
+    );
+    assert_eq!(
+        convert("bogus", OutputFormat::Steam3),
+        Err(STEAMID_ERR_PARSE)
+    );
+
+    unsafe {
+        let mut steam64 = 0;
+        let input = b"[U:1:22202]\0".as_ptr() as *const c_char;
+        assert_eq!(steamid_parse(input, &mut steam64), STEAMID_OK);
+        assert_eq!(steam64, 76561197960287930);
+        assert_eq!(
+            steamid_parse(std::ptr::null(), &mut steam64),
+            STEAMID_ERR_NULL
+        );
+        let mut code = 0;
+        assert_eq!(
+            steamid_parse_with_code(
+                b"bogus\0".as_ptr() as *const c_char,
+                &mut steam64,
+                &mut code
+            ),
+            STEAMID_ERR_PARSE
+        );
+        assert_eq!(code, 1);
+
+        let mut buf = [0 as c_char; 32];
+        assert_eq!(
+            steamid_convert(
+                input,
+                OutputFormat::Steam2 as u32,
+                buf.as_mut_ptr(),
+                buf.len()
+            ),
+            STEAMID_OK
+        );
+        assert_eq!(CStr::from_ptr(buf.as_ptr()).to_str(), Ok("STEAM_1:0:11101"));
+        assert_eq!(
+            steamid_convert(input, 7, buf.as_mut_ptr(), buf.len()),
+            STEAMID_ERR_FORMAT
+        );
+
+        assert_eq!(
+            steamid_render(76561197960287930, 0, buf.as_mut_ptr(), buf.len()),
+            STEAMID_OK
+        );
+        assert_eq!(
+            CStr::from_ptr(buf.as_ptr()).to_str(),
+            Ok("76561197960287930")
+        );
+        // 17 digits don't fit alongside the NUL
+        assert_eq!(
+            steamid_render(76561197960287930, 0, buf.as_mut_ptr(), 17),
+            STEAMID_ERR_BUFFER_TOO_SMALL
+        );
+    }
+}
+
+#[cfg(feature = "fyrox")]
+#[test]
+fn test_fyrox_visit() {
+    use fyrox_core::{
+        reflect::Reflect,
+        visitor::{Visit, Visitor},
+    };
+
+    let mut s = SteamID::from(76561197960287930);
+    let mut visitor = Visitor::new();
+    s.visit("Owner", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    let mut visitor = Visitor::load_binary_from_memory(&data).unwrap();
+    let mut loaded = SteamID::default();
+    loaded.visit("Owner", &mut visitor).unwrap();
+    assert_eq!(loaded, s);
+
+    assert!(Reflect::type_name(&s).ends_with("SteamID"));
+}
+
+#[cfg(feature = "egui")]
+#[test]
+fn test_egui_editor() {
+    let ctx = egui::Context::default();
+    let mut s = SteamID::from(76561197960287930);
+    for _ in 0..2 {
+        let mut output = ctx.run_ui(egui::RawInput::default(), |ui| {
+            let response = ui.add(SteamIDEditor::new(&mut s));
+            assert!(!response.changed());
+        });
+        output.textures_delta.clear();
+    }
+    assert_eq!(s, SteamID::from(76561197960287930));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_bytes_codec() {
+    use bytes::{Buf, BufMut, BytesMut};
+
+    let s = SteamID::from(76561197960287930);
+    let mut buf = BytesMut::new();
+    buf.put_steamid(s);
+    buf.put_u8(0xFF);
+    assert_eq!(&buf[..8], &76561197960287930u64.to_le_bytes());
+
+    let mut buf = buf.freeze();
+    assert_eq!(buf.try_get_steamid(), Ok(s));
+    assert!(buf.try_get_steamid().is_err());
+    // A failed read doesn't consume anything
+    assert_eq!(buf.remaining(), 1);
+
+    let mut buf = &76561197960287930u64.to_le_bytes()[..];
+    assert_eq!(buf.get_steamid(), s);
+}
+
+#[test]
+fn test_bounds() {
+    let individual = SteamID::from(76561197960287930);
+    let clan = SteamID::from(103582791429521412);
+    let beta = SteamID::new(
+        5,
+        Instance::Desktop,
+        AccountType::Individual,
+        Universe::Beta,
+    );
+
+    let mut sorted = vec![beta, clan, individual];
+    sorted.sort();
+    assert_eq!(sorted, vec![individual, clan, beta]);
+
+    let bounds = SteamID::bounds_for_account_type(Universe::Public, AccountType::Individual);
+    assert!(bounds.contains(&individual));
+    assert!(!bounds.contains(&clan));
+    assert!(!bounds.contains(&beta));
+    assert!(bounds.contains(&SteamID::new(
+        u32::MAX,
+        Instance::Web,
+        AccountType::Individual,
+        Universe::Public
+    )));
+
+    let public = SteamID::bounds_for_universe(Universe::Public);
+    assert!(public.contains(&individual) && public.contains(&clan) && !public.contains(&beta));
+
+    let set: BTreeSet<SteamID> = sorted.into_iter().collect();
+    let clans: Vec<_> = set
+        .range(SteamID::bounds_for_account_type(
+            Universe::Public,
+            AccountType::Clan,
+        ))
+        .collect();
+    assert_eq!(clans, vec![&clan]);
+}
+
+#[test]
+fn test_parse_with() {
+    let gabe = SteamID::from(76561197960287930);
+    let strict = ParseOptions::default();
+    for input in ["76561197960287930", "STEAM_1:0:11101", "[U:1:22202]"] {
+        assert_eq!(SteamID::parse_with(input, strict), input.parse());
+    }
+    for input in [
+        " STEAM_1:0:11101",
+        "steam_1:0:11101",
+        "U:1:22202",
+        "[u:1:22202]",
+    ] {
+        assert!(SteamID::parse_with(input, strict).is_err());
+    }
+
+    let lenient = ParseOptions::lenient();
+    for input in [
+        " 76561197960287930\t",
+        " steam_1:0:11101 ",
+        "Steam_1:0:11101",
+        "U:1:22202",
+        "u:1:22202",
+        "[u:1:22202]",
+    ] {
+        assert_eq!(SteamID::parse_with(input, lenient), Ok(gabe), "{}", input);
+    }
+    // Lowercase letters that are types of their own aren't uppercased
+    assert_eq!(
+        SteamID::parse_with("c:1:123", lenient).map(|s| s.instance()),
+        Ok(Instance::FlagClan)
+    );
+
+    let only_steam3 = ParseOptions::new()
+        .allow_steam64(false)
+        .allow_steam2(false)
+        .optional_brackets(true);
+    assert_eq!(SteamID::parse_with("U:1:22202", only_steam3), Ok(gabe));
+    assert_eq!(
+        SteamID::parse_with("STEAM_1:0:11101", only_steam3).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::WrongFormat)
+    );
+    let only_steam64 = ParseOptions::new().allow_steam2(false).allow_steam3(false);
+    assert_eq!(
+        SteamID::parse_with("7656119796028793x", only_steam64).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::UnexpectedCharacter)
+    );
+
+    // Positions point into the original input
+    let err = SteamID::parse_with("  steam_1:2:11101", lenient).unwrap_err();
+    assert_eq!(err.kind(), SteamIDParseErrorKind::UnexpectedCharacter);
+    assert_eq!(err.position(), Some(10));
+    assert_eq!(err.found(), Some('2'));
+    let err = SteamID::parse_with(" [x:1:22202]", lenient).unwrap_err();
+    assert_eq!(err.kind(), SteamIDParseErrorKind::InvalidAccountType);
+    assert_eq!(err.position(), Some(2));
+    assert_eq!(err.found(), Some('x'));
+}
+
+#[test]
+fn test_find_all() {
+    let gabe = SteamID::from(76561197960287930);
+    let valve = SteamID::from(103582791429521412);
+    let text = "appeal from STEAM_0:0:11101 (aka [U:1:22202], 76561197960287930),\n\
+                see https://steamcommunity.com/profiles/76561197960287930/. \
+                Their group: steamcommunity.com/gid/103582791429521412!";
+    let found: Vec<_> = SteamID::find_all(text).collect();
+    let spans: Vec<_> = found
+        .iter()
+        .map(|(range, _)| &text[range.clone()])
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            "STEAM_0:0:11101",
+            "[U:1:22202]",
+            "76561197960287930",
+            "https://steamcommunity.com/profiles/76561197960287930/",
+            "steamcommunity.com/gid/103582791429521412",
+        ]
+    );
+    assert!(found[..4].iter().all(|&(_, steamid)| steamid == gabe));
+    assert_eq!(found[4].1, valve);
+
+    // Nothing that is only part of a word, or just some number
+    let noise = "xSTEAM_0:0:11101 STEAM_0:0:11101x 765611979602879301234 [X:1:2] 12345 \
+                 order 76561197960287930a https://steamcommunity.com/id/gabelogannewell";
+    assert_eq!(SteamID::find_all(noise).count(), 0);
+    // Not even invalid steam64s
+    assert_eq!(SteamID::find_all("ticket 12345678901234567").count(), 0);
+
+    assert_eq!(
+        SteamID::find_all("способ: STEAM_0:0:11101:").collect::<Vec<_>>(),
+        [(14..29, gabe)]
+    );
+}
+
+#[test]
+fn test_parse_many() {
+    let gabe = SteamID::from(76561197960287930);
+    let inputs = vec![
+        "76561197960287930",
+        "STEAM_1:0:11101",
+        "[U:1:22202]",
+        "STEAM_1:2:3",
+    ];
+    let parsed = SteamID::parse_many(inputs.iter().copied());
+    assert_eq!(parsed[..3], [Ok(gabe), Ok(gabe), Ok(gabe)]);
+    assert_eq!(parsed[3], "STEAM_1:2:3".parse::<SteamID>());
+
+    assert_eq!(
+        SteamID::try_parse_many(inputs[..3].iter().copied()),
+        Ok(vec![gabe; 3])
+    );
+    let (index, err) = SteamID::try_parse_many(inputs).unwrap_err();
+    assert_eq!(index, 3);
+    assert_eq!(err.kind(), SteamIDParseErrorKind::UnexpectedCharacter);
+    assert_eq!(SteamID::try_parse_many(Vec::new()), Ok(Vec::new()));
+}
+
+#[test]
+fn test_steamid_lines() {
+    use std::io::{self, BufReader, Read};
+
+    let input = "76561197960287930\r\n  STEAM_1:0:11101  \n\n\t\n[U:1:22202]\nbogus\n[U:1:22203]";
+    let parsed: Vec<_> = SteamIDLines::new(input.as_bytes())
+        .collect::<io::Result<_>>()
+        .unwrap();
+    let gabe = SteamID::from(76561197960287930);
+    assert_eq!(parsed.len(), 5);
+    assert_eq!(parsed[..3], [(1, Ok(gabe)), (2, Ok(gabe)), (5, Ok(gabe))]);
+    assert_eq!(parsed[3].0, 6);
+    assert_eq!(
+        parsed[3].1.map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::WrongFormat)
+    );
+    assert_eq!(parsed[4], (7, Ok(SteamID::from(76561197960287931))));
+
+    // Lines that aren't UTF-8 don't parse, but don't stop the lines after them either
+    let input: &[u8] = b"[U:1:22202]\n[U:1:\xff22202]\n[U:1:22202]\n";
+    let parsed: Vec<_> = SteamIDLines::new(BufReader::new(input))
+        .collect::<io::Result<_>>()
+        .unwrap();
+    assert_eq!(parsed.len(), 3);
+    assert_eq!((parsed[0], parsed[2]), ((1, Ok(gabe)), (3, Ok(gabe))));
+    assert_eq!(parsed[1].0, 2);
+    assert_eq!(
+        parsed[1].1.map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::UnexpectedCharacter)
+    );
+
+    // I/O errors end the stream
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+    let mut lines = SteamIDLines::new(BufReader::new(Failing));
+    assert_eq!(
+        lines.next().unwrap().unwrap_err().kind(),
+        io::ErrorKind::Other
+    );
+    assert!(lines.next().is_none());
+
+    let mut rest = String::new();
+    SteamIDLines::new(BufReader::new(&b"x"[..]))
+        .into_inner()
+        .read_to_string(&mut rest)
+        .unwrap();
+    assert_eq!(rest, "x");
+}
+
+#[test]
+fn test_parse_suggestions() {
+    let suggestion = |s: &str| {
+        assert!(s.parse::<SteamID>().is_err());
+        suggest::any(s)
+    };
+
+    assert_eq!(
+        suggestion("STEAM1:0:11101").as_deref(),
+        Some("STEAM_1:0:11101")
+    );
+    assert_eq!(
+        suggestion("steam_1:0:11101").as_deref(),
+        Some("STEAM_1:0:11101")
+    );
+    assert_eq!(
+        suggestion("STEAM_1;0;11101").as_deref(),
+        Some("STEAM_1:0:11101")
+    );
+    assert_eq!(suggestion("[U:1:22202").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggestion("U:1:22202").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggestion("[u:1:22202]").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggestion("[U;1;22202]").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggestion(" [U:1:22202] ").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggestion("bogus"), None);
+    assert_eq!(suggestion("STEAM_9:0:1"), None);
+
+    let err = SteamID::from_steam2("STEAM1:0:11101").unwrap_err();
+    assert_eq!(err.to_string(), "Malformed SteamID: unrecognized format");
+    assert_eq!(err.code(), 1);
+    assert_eq!(
+        suggest::steam2("STEAM1:0:11101").as_deref(),
+        Some("STEAM_1:0:11101")
+    );
+    // Each format only suggests corrections in its own format
+    assert_eq!(suggest::steam2("U:1:22202"), None);
+    assert_eq!(suggest::steam3("U:1:22202").as_deref(), Some("[U:1:22202]"));
+    assert_eq!(suggest::steam3("STEAM1:0:11101"), None);
+    assert_eq!(
+        SteamID::from_steam3("[Q:1:22202]").unwrap_err().to_string(),
+        "Malformed SteamID: invalid account type at byte 1"
+    );
+}
+
+#[test]
+fn test_sourcebans() {
+    use std::time::Duration;
+    use steamid_ng::sourcebans::*;
+
+    let csv = "bid,authid,name,length,reason\r\n\
+               1,STEAM_0:0:11101,gabe,0,\"Cheating, obviously\"\r\n\
+               \r\n\
+               2,STEAM_0:1:4491990,\"some \"\"guy\"\"\",3600,\"multi\nline\"\r\n";
+    let bans = parse_csv(csv).unwrap();
+    assert_eq!(
+        bans,
+        vec![
+            SourceBansBan {
+                steamid: SteamID::from(76561197960287930),
+                reason: "Cheating, obviously".to_owned(),
+                duration: None,
+            },
+            SourceBansBan {
+                steamid: SteamID::from_steam2("STEAM_0:1:4491990").unwrap(),
+                reason: "multi\nline".to_owned(),
+                duration: Some(Duration::from_secs(3600)),
+            },
+        ]
+    );
+
+    let emitted = to_csv(&bans).unwrap();
+    assert_eq!(
+        emitted,
+        "authid,reason,length\n\
+         STEAM_0:0:11101,\"Cheating, obviously\",0\n\
+         STEAM_0:1:4491990,\"multi\nline\",3600\n"
+    );
+    assert_eq!(parse_csv(&emitted).unwrap(), bans);
+
+    let json = serde_json::to_string(&bans[0]).unwrap();
+    assert_eq!(
+        json,
+        r#"{"authid":"STEAM_0:0:11101","reason":"Cheating, obviously","length":0}"#
+    );
+    let parsed: Vec<SourceBansBan> = serde_json::from_str(
+        r#"[{"bid": "2", "authid": "STEAM_0:1:4491990", "reason": "multi\nline", "length": "3600"}]"#,
+    )
+    .unwrap();
+    assert_eq!(parsed[..], bans[1..]);
+
+    // Sub-second remainders round up rather than down to a permanent ban
+    for (duration, length) in [
+        (Duration::from_millis(500), "1"),
+        (Duration::ZERO, "1"),
+        (Duration::from_millis(3_600_001), "3601"),
+    ] {
+        let ban = SourceBansBan {
+            duration: Some(duration),
+            ..bans[0].clone()
+        };
+        assert_eq!(
+            to_csv(std::slice::from_ref(&ban)).unwrap(),
+            format!(
+                "authid,reason,length\nSTEAM_0:0:11101,\"Cheating, obviously\",{}\n",
+                length
+            )
+        );
+        assert!(serde_json::to_string(&ban)
+            .unwrap()
+            .ends_with(&format!(r#""length":{}}}"#, length)));
+    }
+
+    // The universe doesn't survive the round trip, and other account types can't be written
+    let beta = SourceBansBan {
+        steamid: SteamID::new(
+            11101 * 2,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Beta,
+        ),
+        ..bans[0].clone()
+    };
+    assert_eq!(
+        parse_csv(&to_csv(std::slice::from_ref(&beta)).unwrap()).unwrap(),
+        bans[..1]
+    );
+    let clan = SourceBansBan {
+        steamid: SteamID::from(103582791429521412),
+        ..bans[0].clone()
+    };
+    assert_eq!(
+        to_csv(&[bans[0].clone(), clan.clone()])
+            .unwrap_err()
+            .account_type(),
+        AccountType::Clan
+    );
+    assert!(serde_json::to_string(&clan).is_err());
+
+    assert_eq!(parse_csv("authid,reason\n").unwrap_err().line(), 1);
+    assert_eq!(
+        parse_csv("authid,reason,length\nSTEAM_0:0:1,x,0\nbogus,x,0\n")
+            .unwrap_err()
+            .line(),
+        3
+    );
+    assert_eq!(
+        parse_csv("authid,reason,length\nSTEAM_0:0:1,\"x,0\n")
+            .unwrap_err()
+            .line(),
+        2
+    );
+}
+
+#[test]
+fn test_status() {
+    use std::time::Duration;
+    use steamid_ng::status::*;
+
+    let output = r#"hostname: Team Fortress
+version : 8622567/24 8622567 secure
+players : 3 humans, 1 bots (24 max)
+# userid name                uniqueid            connected ping loss state  adr
+#      2 "Gabe"              [U:1:22202]         1:05:42     45    0 active 192.168.1.2:27005
+#      3 "Bob"               BOT                                     active
+#      4 "say "hi""          STEAM_0:1:11101     00:07      120    2 spawning 10.0.0.5:27005
+#      5 "Host"              [U:1:22204]         12:00        0    0 active loopback
+#      6 "New"               STEAM_ID_PENDING    00:01       80    0 connected 10.0.0.6:27005
+"#;
+    let players = parse(output);
+    assert_eq!(players.len(), 5);
+    assert_eq!(
+        players[0],
+        StatusPlayer {
+            userid: 2,
+            name: "Gabe".to_owned(),
+            steamid: SteamID::from(76561197960287930),
+            connected: Some(Duration::from_secs(3942)),
+            ping: Some(45),
+            address: "192.168.1.2:27005".parse().ok(),
+        }
+    );
+    assert_eq!(players[1].name, "Bob");
+    assert_eq!(players[1].steamid.account_type(), AccountType::AnonUser);
+    assert_eq!(players[1].connected, None);
+    assert_eq!(players[2].name, r#"say "hi""#);
+    assert_eq!(players[2].steamid, SteamID::from(76561197960287931));
+    assert_eq!(players[2].connected, Some(Duration::from_secs(7)));
+    assert_eq!(players[3].address, None);
+    assert_eq!(players[4].steamid.account_type(), AccountType::Pending);
+
+    // CS:GO has a slot column before the name
+    let players = parse(
+        r#"# userid name uniqueid connected ping loss state rate adr
+# 12 1 "Gabe" STEAM_1:0:11101 10:00 30 0 active 196608 192.168.1.2:27005
+#end"#,
+    );
+    assert_eq!(players.len(), 1);
+    assert_eq!(players[0].userid, 12);
+    assert_eq!(players[0].address, "192.168.1.2:27005".parse().ok());
+    assert_eq!(players[0].steamid, SteamID::from(76561197960287930));
+}
+
+#[test]
+fn test_vdf() {
+    use steamid_ng::vdf::*;
+
+    let gabe = SteamID::from(76561197960287930);
+    let text = r#"// admins
+"Admins"
+{
+    "gabe"
+    {
+        "auth"      "steam"
+        "identity"  "STEAM_0:0:11101"
+        "flags"     "z"
+        "immunity"  "99"
+    }
+    "console" { "identity" [U:1:22202] "flags" "\"quoted\"" }
+    "76561197960287930" "1"  [$WIN32]
+    "lastseen" "1700000000"
+}
+"#;
+    let found = find_steamids(text);
+    let paths: Vec<Vec<&str>> = found
+        .iter()
+        .map(|entry| entry.path.iter().map(String::as_str).collect())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            vec!["Admins", "gabe", "identity"],
+            vec!["Admins", "console", "identity"],
+            vec!["Admins", "76561197960287930"],
+        ]
+    );
+    assert!(found.iter().all(|entry| entry.steamid == gabe));
+
+    // Unbalanced documents are read as far as possible
+    let found = find_steamids("} \"a\" { \"b\" \"[U:1:22202]\" ");
+    assert_eq!(
+        found,
+        [VdfEntry {
+            path: vec!["a".to_owned(), "b".to_owned()],
+            steamid: gabe,
+        }]
+    );
+    assert!(find_steamids("").is_empty());
+}
+
+#[test]
+fn test_banned_user_cfg() {
+    use std::time::Duration;
+    use steamid_ng::bans::*;
+
+    let gabe = SteamID::from(76561197960287930);
+    let cfg = "// banned_user.cfg\n\
+               banid 0 STEAM_0:0:11101\n\
+               \n\
+               BANID 1440 STEAM_0:1:11101 kick\r\n\
+               banid 0.5 [U:1:22202]\n";
+    let bans = parse_banned_user_cfg(cfg).unwrap();
+    assert_eq!(
+        bans,
+        [
+            Ban {
+                steamid: gabe,
+                duration: None,
+            },
+            Ban {
+                steamid: SteamID::from(76561197960287931),
+                duration: Some(Duration::from_secs(86400)),
+            },
+            Ban {
+                steamid: gabe,
+                duration: Some(Duration::from_secs(30)),
+            },
+        ]
+    );
+    assert_eq!(
+        to_banned_user_cfg(&bans).unwrap(),
+        "banid 0 STEAM_0:0:11101\nbanid 1440 STEAM_0:1:11101\nbanid 1 STEAM_0:0:11101\n"
+    );
+    assert_eq!(
+        parse_banned_user_cfg(&to_banned_user_cfg(&bans[..2]).unwrap()).unwrap(),
+        bans[..2]
+    );
+
+    assert_eq!(
+        parse_banid("banid 5 \"STEAM_0:0:11101\"").unwrap().steamid,
+        gabe
+    );
+    for bad in [
+        "banid 5 12",
+        "banid -1 STEAM_0:0:11101",
+        "banid inf STEAM_0:0:11101",
+        "banid 1e300 STEAM_0:0:11101",
+        // Within u64::MAX / 60 as an f64, but too long for a Duration in seconds
+        "banid 307445734561825860 STEAM_0:0:1",
+        "banid 5 STEAM_0:0:11101 now",
+        "banid 5",
+        "kick STEAM_0:0:11101",
+    ] {
+        assert_eq!(parse_banid(bad).map_err(|e| e.line()), Err(1), "{}", bad);
+    }
+    assert_eq!(
+        parse_banned_user_cfg("banid 0 STEAM_0:0:11101\n\nbanid 0 nobody\n")
+            .unwrap_err()
+            .line(),
+        3
+    );
+}
+
+#[test]
+fn test_ban_commands() {
+    use std::time::Duration;
+    use steamid_ng::{bans, bans::Ban, commands};
+
+    let ban = Ban {
+        steamid: SteamID::from(76561197960287931),
+        duration: Some(Duration::from_secs(90)),
+    };
+    assert_eq!(
+        commands::banid(&ban, false).unwrap(),
+        "banid 2 STEAM_0:1:11101"
+    );
+    assert_eq!(
+        commands::banid(&ban, true).unwrap(),
+        "banid 2 STEAM_0:1:11101 kick"
+    );
+    assert_eq!(
+        bans::parse_banid(&commands::banid(&ban, true).unwrap()).map(|b| b.steamid),
+        Ok(ban.steamid)
+    );
+    assert_eq!(
+        commands::sm_ban(&ban, "Wallhack").unwrap(),
+        r##"sm_ban "#STEAM_0:1:11101" 2 "Wallhack""##
+    );
+
+    // Rounding is on the whole duration, and never turns a timed ban permanent
+    for (duration, minutes) in [
+        (Duration::from_millis(500), 1),
+        (Duration::ZERO, 1),
+        (Duration::from_millis(60_500), 2),
+        (Duration::from_secs(60), 1),
+    ] {
+        let ban = Ban {
+            duration: Some(duration),
+            ..ban
+        };
+        assert_eq!(
+            commands::banid(&ban, false).unwrap(),
+            format!("banid {} STEAM_0:1:11101", minutes)
+        );
+    }
+    let short = Ban {
+        duration: Some(Duration::from_millis(500)),
+        ..ban
+    };
+    assert_eq!(
+        bans::to_banned_user_cfg(&[short]).unwrap(),
+        "banid 1 STEAM_0:1:11101\n"
+    );
+
+    let permanent = Ban {
+        duration: None,
+        ..ban
+    };
+    assert_eq!(
+        commands::sm_addban(&permanent, "Spam\"; quit\n").unwrap(),
+        r#"sm_addban 0 "STEAM_0:1:11101" "Spam quit""#
+    );
+    assert_eq!(
+        commands::sm_addban(&permanent, "").unwrap(),
+        r#"sm_addban 0 "STEAM_0:1:11101" """#
+    );
+
+    // Nothing but individuals has a steam2 ID to ban
+    let clan = Ban {
+        steamid: SteamID::from(103582791429521412),
+        ..ban
+    };
+    assert_eq!(
+        commands::banid(&clan, true).unwrap_err().account_type(),
+        AccountType::Clan
+    );
+    assert!(commands::sm_ban(&clan, "").is_err());
+    assert!(commands::sm_addban(&clan, "").is_err());
+    assert!(bans::to_banned_user_cfg(&[ban, clan]).is_err());
+}
+
+#[cfg(feature = "md5")]
+#[test]
+fn test_battleye_guid() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.battleye_guid(), "a357f31c8335a5263e0d816e64445b6a");
+    assert_eq!(
+        SteamID::from(76561197960287931).battleye_guid(),
+        "3198a9961623d572ec1620949bef16f5"
+    );
+    assert!(s.matches_battleye_guid("A357F31C8335A5263E0D816E64445B6A"));
+    assert!(!s.matches_battleye_guid("a357f31c8335a5263e0d816e64445b6"));
+}
+
+#[cfg(feature = "pseudonymize")]
+#[test]
+fn test_pseudonymize() {
+    use steamid_ng::pseudonymize::{Pseudonym, PseudonymTable, Pseudonymizer};
+
+    let a = SteamID::from(76561197960287930);
+    let b = SteamID::from(76561197960287931);
+    let pseudonymizer = Pseudonymizer::new([1; 32]);
+    assert_eq!(pseudonymizer.pseudonymize(a), pseudonymizer.pseudonymize(a));
+    assert_ne!(pseudonymizer.pseudonymize(a), pseudonymizer.pseudonymize(b));
+    assert_ne!(
+        pseudonymizer.pseudonymize(a),
+        Pseudonymizer::new([2; 32]).pseudonymize(a)
+    );
+    assert_eq!(
+        Pseudonymizer::derive("steamid-ng tests", b"hunter2").pseudonymize(a),
+        Pseudonymizer::derive("steamid-ng tests", b"hunter2").pseudonymize(a)
+    );
+    assert_eq!(format!("{:?}", pseudonymizer), "Pseudonymizer { .. }");
+
+    let pseudonym = pseudonymizer.pseudonymize(a);
+    let hex = pseudonym.to_string();
+    assert_eq!(hex.len(), 64);
+    assert!(hex
+        .bytes()
+        .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase()));
+    assert_eq!(Pseudonym::from(*pseudonym.as_bytes()), pseudonym);
+
+    let mut table = PseudonymTable::new(pseudonymizer.clone());
+    assert!(table.is_empty());
+    assert_eq!(table.pseudonymize(a), pseudonym);
+    table.pseudonymize(a);
+    table.pseudonymize(b);
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.lookup(&pseudonym), Some(a));
+    assert_eq!(
+        table.lookup(&Pseudonymizer::new([2; 32]).pseudonymize(a)),
+        None
+    );
+}
+
+#[test]
+fn test_interval_set() {
+    let id = |n: u64| SteamID::from(76561197960265728 + n);
+    let ranges = |set: &SteamIDIntervalSet| set.iter().collect::<Vec<_>>();
+
+    let mut set = SteamIDIntervalSet::new();
+    assert!(set.is_empty());
+    set.insert_range(id(10)..=id(20));
+    set.insert_range(id(30)..=id(40));
+    set.insert_range(id(5)..=id(1));
+    assert_eq!(ranges(&set), vec![id(10)..=id(20), id(30)..=id(40)]);
+    assert!(set.contains(id(10)) && set.contains(id(20)) && set.contains(id(35)));
+    assert!(!set.contains(id(9)) && !set.contains(id(21)) && !set.contains(id(41)));
+
+    // Adjacent ranges merge, as do ranges bridging several others
+    set.insert(id(21));
+    assert_eq!(ranges(&set), vec![id(10)..=id(21), id(30)..=id(40)]);
+    set.insert_range(id(50)..=id(60));
+    set.insert_range(id(15)..=id(55));
+    assert_eq!(ranges(&set), vec![id(10)..=id(60)]);
+    set.insert_range(id(12)..=id(14));
+    assert_eq!(ranges(&set), vec![id(10)..=id(60)]);
+
+    set.insert_range(SteamID::from(u64::MAX - 1)..=SteamID::from(u64::MAX));
+    set.insert(SteamID::from(0));
+    assert!(set.contains(SteamID::from(u64::MAX)) && set.contains(SteamID::from(0)));
+
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(
+        json,
+        "[[0,0],[76561197960265738,76561197960265788],[18446744073709551614,18446744073709551615]]"
+    );
+    assert_eq!(
+        serde_json::from_str::<SteamIDIntervalSet>(&json).unwrap(),
+        set
+    );
+    let overlapping: SteamIDIntervalSet =
+        serde_json::from_str(r#"[["[U:1:1]", "[U:1:5]"], [76561197960265731, 76561197960265740]]"#)
+            .unwrap();
+    assert_eq!(ranges(&overlapping), vec![id(1)..=id(12)]);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_log_kv() {
+    use log::kv::{ToValue, Value};
+
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.to_value().to_u64(), Some(76561197960287930));
+    assert_eq!(Value::from(s).to_u64(), Some(76561197960287930));
+}
+
+#[test]
+fn test_parse_error_kinds() {
+    use SteamIDParseErrorKind::*;
+
+    let steam2 = |s: &str| SteamID::from_steam2(s).unwrap_err().kind();
+    assert_eq!(steam2("[U:1:22202]"), WrongFormat);
+    assert_eq!(steam2("STEAM_1-0:11101"), UnexpectedCharacter);
+    assert_eq!(steam2("STEAM_1:2:11101"), UnexpectedCharacter);
+    assert_eq!(steam2("STEAM_1:0:11101x"), UnexpectedCharacter);
+    assert_eq!(steam2("STEAM_1:0:"), UnexpectedEnd);
+    assert_eq!(steam2("STEAM_7:0:11101"), InvalidUniverse);
+    assert_eq!(steam2("STEAM_1:0:4294967296"), AccountIdOverflow);
+    assert_eq!(steam2("STEAM_1:0:00000000001"), AccountIdOverflow);
+
+    let steam3 = |s: &str| SteamID::from_steam3(s).unwrap_err().kind();
+    assert_eq!(steam3("STEAM_1:0:11101"), WrongFormat);
+    assert_eq!(steam3("[U:1:22202"), UnexpectedEnd);
+    assert_eq!(steam3("[U:1:22202:1:1]"), UnexpectedCharacter);
+    assert_eq!(steam3("[X:1:22202]"), InvalidAccountType);
+    assert_eq!(steam3("[U:5:22202]"), InvalidUniverse);
+    assert_eq!(steam3("[U:1:22202:1048576]"), InvalidInstance);
+    assert_eq!(steam3("[U:1:4294967296]"), AccountIdOverflow);
+
+    let from_str = |s: &str| s.parse::<SteamID>().unwrap_err();
+    assert_eq!(from_str("bogus").kind(), WrongFormat);
+    assert_eq!(from_str("STEAM_7:0:1").kind(), InvalidUniverse);
+    assert_eq!(from_str("[U:1:22202").kind(), UnexpectedEnd);
+    assert_eq!(from_str("[U:1:22202").code(), 3);
+    assert_eq!(from_str("bogus").code(), 1);
+
+    assert_eq!(
+        SteamID::from_steam64(76561197960287930),
+        Ok(SteamID::from(76561197960287930))
+    );
+    let lobby = SteamID::from(109775241017770404);
+    assert_eq!(SteamID::from_steam64(lobby.into()), Ok(lobby));
+    assert_eq!(
+        SteamID::from_steam64(0x0510_0001_0000_56BA)
+            .unwrap_err()
+            .kind(),
+        InvalidUniverse
+    );
+    let future_type = SteamID::from_steam64(0x01F0_0001_0000_56BA).unwrap();
+    assert_eq!(future_type.account_type(), AccountType::Invalid);
+    assert_eq!(future_type.account_type_value(), 15);
+    assert_eq!(u64::from(future_type), 0x01F0_0001_0000_56BA);
+    assert_eq!(
+        SteamID::from_steam64(0x0110_0003_0000_56BA)
+            .unwrap_err()
+            .kind(),
+        InvalidInstance
+    );
+
+    // Anonymous game servers are numbered by instance
+    let anon = u64::from(AnonGameServerID::new(1234, 5678).unwrap().steamid());
+    assert_eq!(SteamID::from_steam64(anon), Ok(SteamID::from(anon)));
+    assert_eq!(
+        SteamID::from_steam64_any_universe(anon),
+        Ok(SteamID::from(anon))
+    );
+    assert!(SteamID::from(anon)
+        .validate(ValidationLevel::BitfieldsOnly)
+        .is_ok());
+
+    // Only chats may set the flag bits, and only the flag bits
+    assert_eq!(
+        SteamID::from_steam64(109775241017770404).unwrap().steam3(),
+        "[L:1:100614564:393216]"
+    );
+    for junk in [
+        0x0110_0004_0000_56BA | 0x8_0000 << 32,
+        0x0180_0001_0000_56BA | 0x1000 << 32,
+    ] {
+        assert_eq!(
+            SteamID::from_steam64(junk).unwrap_err().kind(),
+            InvalidInstance,
+            "{:x}",
+            junk
+        );
+    }
+}
+
+#[test]
+fn test_parse_error_position() {
+    let err = |r: Result<SteamID, SteamIDParseError>| {
+        let e = r.unwrap_err();
+        (e.kind(), e.position(), e.found())
+    };
+    use SteamIDParseErrorKind::*;
+
+    assert_eq!(
+        err(SteamID::from_steam2("STEAM_1:0:11x01")),
+        (UnexpectedCharacter, Some(12), Some('x'))
+    );
+    assert_eq!(
+        err(SteamID::from_steam2("STEAM_1:0")),
+        (UnexpectedEnd, Some(9), None)
+    );
+    assert_eq!(
+        err(SteamID::from_steam2("STEAM_8:0:1")),
+        (InvalidUniverse, Some(6), Some('8'))
+    );
+    assert_eq!(
+        err(SteamID::from_steam2("STEAM_1:0:99999999999")),
+        (AccountIdOverflow, Some(10), Some('9'))
+    );
+    assert_eq!(
+        err(SteamID::from_steam3("[U:1:22202;1]")),
+        (UnexpectedCharacter, Some(10), Some(';'))
+    );
+    assert_eq!(
+        err(SteamID::from_steam3("[é:1:22202]")),
+        (InvalidAccountType, Some(1), Some('é'))
+    );
+    assert_eq!(
+        err(SteamID::from_steam3("[U:1:1:9999999]")),
+        (InvalidInstance, Some(7), Some('9'))
+    );
+    assert_eq!(err("bogus".parse()), (WrongFormat, Some(0), Some('b')));
+    assert_eq!(
+        err(SteamID::from_steam64(u64::MAX)),
+        (InvalidUniverse, None, None)
+    );
+
+    assert_eq!(
+        SteamID::from_steam2("STEAM_1:0:11x01")
+            .unwrap_err()
+            .to_string(),
+        "Malformed SteamID: unexpected character 'x' at byte 12"
+    );
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn test_miette_diagnostic() {
+    use miette::Diagnostic;
+
+    let err = SteamID::from_steam2("STEAM_1:0:11x01").unwrap_err();
+    assert_eq!(
+        Diagnostic::code(&err).unwrap().to_string(),
+        "steamid_ng::unexpected_character"
+    );
+    let labels: Vec<_> = err.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!((labels[0].offset(), labels[0].len()), (12, 1));
+    assert_eq!(labels[0].label(), Some("unexpected character"));
+    assert!(err.help().is_none());
+
+    let err = SteamID::from_steam3("[U:1:22202").unwrap_err();
+    let labels: Vec<_> = err.labels().unwrap().collect();
+    assert_eq!((labels[0].offset(), labels[0].len()), (10, 0));
+
+    assert!(SteamID::from_steam64(u64::MAX)
+        .unwrap_err()
+        .labels()
+        .is_none());
+}
+
+#[cfg(feature = "friend-code")]
+#[test]
+fn test_friend_code() {
+    let cases = [
+        (22202, "SUCVS-FADA"),
+        (1, "AJJJS-ABAA"),
+        (0, "AEJJS-ABCA"),
+        (4294967295, "S9ZZR-999P"),
+        (123456789, "ANX85-NLCN"),
+    ];
+    for &(account_id, code) in cases.iter() {
+        let s = SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        );
+        assert_eq!(s.friend_code().as_deref(), Some(code));
+        assert_eq!(SteamID::from_friend_code(code), Ok(s));
+    }
+
+    assert_eq!(SteamID::from(103582791429521412).friend_code(), None);
+
+    let kind = |code: &str| SteamID::from_friend_code(code).unwrap_err().kind();
+    assert_eq!(kind("SUCVS-FADB"), SteamIDParseErrorKind::ChecksumMismatch);
+    assert_eq!(kind("SUCVS-FAD"), SteamIDParseErrorKind::UnexpectedEnd);
+    assert_eq!(
+        kind("SUCVS-FADAA"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+    assert_eq!(
+        kind("SUCVSFADA"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+    let err = SteamID::from_friend_code("SUCVS-F1DA").unwrap_err();
+    assert_eq!((err.position(), err.found()), (Some(7), Some('1')));
+}
+
+#[test]
+fn test_invite_code() {
+    let individual = |account_id| {
+        SteamID::new(
+            account_id,
+            Instance::Desktop,
+            AccountType::Individual,
+            Universe::Public,
+        )
+    };
+    let cases = [
+        (22202, "hj-qp"),
+        (0, "b"),
+        (1, "c"),
+        (123456789, "khq-rtch"),
+        (u32::MAX, "wwww-wwww"),
+    ];
+    for &(account_id, code) in cases.iter() {
+        assert_eq!(individual(account_id).invite_code().as_deref(), Some(code));
+        assert_eq!(SteamID::from_invite_code(code), Ok(individual(account_id)));
+    }
+
+    assert_eq!(
+        SteamID::from(76561197960287930).invite_url().as_deref(),
+        Some("https://s.team/p/hj-qp")
+    );
+    assert_eq!(SteamID::from(103582791429521412).invite_code(), None);
+    for url in [
+        "https://s.team/p/hj-qp",
+        "http://s.team/p/hj-qp/",
+        "s.team/p/hjqp",
+        "https://s.team/p/hj-qp/AbCdEf12",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            SteamID::from_invite_code(url),
+            Ok(individual(22202)),
+            "{}",
+            url
+        );
+    }
+
+    let kind = |code: &str| SteamID::from_invite_code(code).unwrap_err().kind();
+    assert_eq!(kind(""), SteamIDParseErrorKind::UnexpectedEnd);
+    assert_eq!(
+        kind("https://s.team/p/"),
+        SteamIDParseErrorKind::UnexpectedEnd
+    );
+    assert_eq!(kind("hj-qa"), SteamIDParseErrorKind::UnexpectedCharacter);
+    assert_eq!(kind("cwwww-wwww"), SteamIDParseErrorKind::AccountIdOverflow);
+}
+
+#[test]
+fn test_community_url() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(
+        s.community_url(),
+        "https://steamcommunity.com/profiles/76561197960287930"
+    );
+
+    for url in [
+        "https://steamcommunity.com/profiles/76561197960287930",
+        "https://steamcommunity.com/profiles/76561197960287930/",
+        "http://www.steamcommunity.com/profiles/76561197960287930/inventory/",
+        "steamcommunity.com/profiles/76561197960287930?l=english",
+        "https://steamcommunity.com/profiles/76561197960287930#comments",
+        "https://steamcommunity.com/profiles/[U:1:22202]/",
+    ]
+    .iter()
+    {
+        assert_eq!(SteamID::from_community_url(url), Ok(s), "{}", url);
+    }
+
+    let err = |url: &str| {
+        let e = SteamID::from_community_url(url).unwrap_err();
+        (e.kind(), e.position())
+    };
+    use SteamIDParseErrorKind::*;
+    assert_eq!(
+        err("https://steamcommunity.com/id/gabelogannewell"),
+        (WrongFormat, Some(0))
+    );
+    assert_eq!(
+        err("https://example.com/profiles/76561197960287930"),
+        (WrongFormat, Some(0))
+    );
+    assert_eq!(
+        err("https://steamcommunity.com/profiles/"),
+        (UnexpectedEnd, Some(36))
+    );
+    assert_eq!(
+        err("https://steamcommunity.com/profiles/7656119796x"),
+        (UnexpectedCharacter, Some(46))
+    );
+    assert_eq!(
+        err("https://steamcommunity.com/profiles/765611979602879300000"),
+        (WrongFormat, Some(36))
+    );
+    assert_eq!(
+        err("https://steamcommunity.com/profiles/[U:9:22202]"),
+        (InvalidUniverse, Some(39))
+    );
+}
+
+#[test]
+fn test_group_url() {
+    let clan = SteamID::from(103582791432294076);
+    assert_eq!(
+        clan.group_url().as_deref(),
+        Some("https://steamcommunity.com/gid/103582791432294076")
+    );
+    assert_eq!(SteamID::from(76561197960287930).group_url(), None);
+
+    for url in [
+        "https://steamcommunity.com/gid/103582791432294076",
+        "steamcommunity.com/gid/103582791432294076/members?p=2",
+        "https://steamcommunity.com/gid/[g:1:2772668]",
+    ]
+    .iter()
+    {
+        assert_eq!(SteamID::from_group_url(url), Ok(clan), "{}", url);
+    }
+
+    let err =
+        SteamID::from_group_url("https://steamcommunity.com/gid/76561197960287930").unwrap_err();
+    assert_eq!(
+        (err.kind(), err.position()),
+        (SteamIDParseErrorKind::InvalidAccountType, Some(31))
+    );
+    assert_eq!(
+        SteamID::from_group_url("https://steamcommunity.com/groups/valve")
+            .unwrap_err()
+            .kind(),
+        SteamIDParseErrorKind::WrongFormat
+    );
+}
+
+#[test]
+fn test_trade_url() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(
+        s.trade_url_partner_param().as_deref(),
+        Some("partner=22202")
+    );
+    assert_eq!(
+        SteamID::from(103582791429521412).trade_url_partner_param(),
+        None
+    );
+
+    for url in [
+        "https://steamcommunity.com/tradeoffer/new/?partner=22202&token=abcdEFGH",
+        "https://steamcommunity.com/tradeoffer/new?token=abcdEFGH&partner=22202",
+        "steamcommunity.com/tradeoffer/new/?partner=22202#offer",
+    ] {
+        assert_eq!(SteamID::from_trade_url(url), Ok(s), "{}", url);
+    }
+
+    let kind = |url: &str| SteamID::from_trade_url(url).unwrap_err().kind();
+    assert_eq!(
+        kind("https://steamcommunity.com/profiles/76561197960287930"),
+        SteamIDParseErrorKind::WrongFormat
+    );
+    assert_eq!(
+        kind("https://steamcommunity.com/tradeoffer/new/?token=abcdEFGH"),
+        SteamIDParseErrorKind::WrongFormat
+    );
+    assert_eq!(
+        kind("https://steamcommunity.com/tradeoffer/new/?partner=99999999999"),
+        SteamIDParseErrorKind::AccountIdOverflow
+    );
+    let err = SteamID::from_trade_url("steamcommunity.com/tradeoffer/new/?token=x&partner=12a")
+        .unwrap_err();
+    assert_eq!(err.kind(), SteamIDParseErrorKind::UnexpectedCharacter);
+    assert_eq!(err.position(), Some(53));
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn test_url() {
+    use url::Url;
+
+    let s = SteamID::from(76561197960287930);
+    let clan = SteamID::from(103582791429521412);
+    assert_eq!(
+        s.to_url().as_str(),
+        "https://steamcommunity.com/profiles/76561197960287930"
+    );
+    assert_eq!(SteamID::from_url(&s.to_url()), Ok(s));
+    assert_eq!(s.to_group_url(), None);
+    assert_eq!(
+        clan.to_group_url().unwrap().as_str(),
+        "https://steamcommunity.com/gid/103582791429521412"
+    );
+    assert_eq!(SteamID::from_url(&clan.to_group_url().unwrap()), Ok(clan));
+
+    let trade = s.to_trade_url("abc&EF").unwrap();
+    assert_eq!(
+        trade.as_str(),
+        "https://steamcommunity.com/tradeoffer/new/?partner=22202&token=abc%26EF"
+    );
+    assert_eq!(SteamID::from_url(&trade), Ok(s));
+    assert_eq!(clan.to_trade_url("x"), None);
+
+    let url = Url::parse("HTTPS://www.SteamCommunity.com/profiles/[U:1:22202]/?l=en").unwrap();
+    assert_eq!(SteamID::from_url(&url), Ok(s));
+    let url = Url::parse("https://example.com/profiles/76561197960287930").unwrap();
+    assert_eq!(
+        SteamID::from_url(&url).map_err(|e| e.kind()),
+        Err(SteamIDParseErrorKind::WrongFormat)
+    );
+}
+
+#[test]
+fn test_steam_url() {
+    let s = SteamID::from(76561197960287930);
+    let clan = SteamID::from(103582791432294076);
+    assert_eq!(s.friends_add_url(), "steam://friends/add/76561197960287930");
+    assert_eq!(
+        s.steam_id_page_url(),
+        "steam://url/SteamIDPage/76561197960287930"
+    );
+    assert_eq!(
+        clan.steam_id_page_url(),
+        "steam://url/GroupSteamIDPage/103582791432294076"
+    );
+
+    assert_eq!(SteamID::from_steam_url(&s.friends_add_url()), Ok(s));
+    assert_eq!(SteamID::from_steam_url(&s.steam_id_page_url()), Ok(s));
+    assert_eq!(SteamID::from_steam_url(&clan.steam_id_page_url()), Ok(clan));
+    assert_eq!(
+        SteamID::from_steam_url("steam://friends/message/76561197960287930/"),
+        Ok(s)
+    );
+
+    let kind = |url: &str| SteamID::from_steam_url(url).unwrap_err().kind();
+    assert_eq!(
+        kind("steam://joinlobby/730/109775241017770404"),
+        SteamIDParseErrorKind::WrongFormat
+    );
+    assert_eq!(
+        kind("steam://friends/add/"),
+        SteamIDParseErrorKind::UnexpectedEnd
+    );
+    assert_eq!(
+        kind("steam://friends/add/76561197960287930x"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+}
+
+#[test]
+fn test_steam_hex() {
+    let s = SteamID::from(76561197960287930);
+    assert_eq!(s.steam_hex(), "steam:1100001000056ba");
+    assert_eq!(SteamID::from_steam_hex(&s.steam_hex()), Ok(s));
+    assert_eq!(SteamID::from_steam_hex("1100001000056ba"), Ok(s));
+    assert_eq!(SteamID::from_steam_hex("steam:1100001000056BA"), Ok(s));
+    assert_eq!(SteamID::from(0).steam_hex(), "steam:0");
+
+    let kind = |s: &str| SteamID::from_steam_hex(s).unwrap_err().kind();
+    assert_eq!(kind("steam:"), SteamIDParseErrorKind::UnexpectedEnd);
+    assert_eq!(kind(""), SteamIDParseErrorKind::UnexpectedEnd);
+    assert_eq!(
+        kind("steam:1100001000056bx"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+    assert_eq!(
+        kind("license:1100001000056ba"),
+        SteamIDParseErrorKind::UnexpectedCharacter
+    );
+    assert_eq!(
+        kind("steam:11000010000056ba0"),
+        SteamIDParseErrorKind::WrongFormat
+    );
+}
+
+// Answers one request per `(status, body)` on a local port, handing back their request lines
+#[cfg(feature = "webapi")]
+async fn serve(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..n]).into_owned();
+            requests.push(request.lines().next().unwrap().to_owned());
+        }
+        requests
+    });
+    (base_url, handle)
+}
+
+// Like `serve`, for a single 200 response
+#[cfg(feature = "webapi")]
+async fn serve_once(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let (base_url, handle) = serve(vec![(200, body)]).await;
+    let handle = tokio::spawn(async move { handle.await.unwrap().remove(0) });
+    (base_url, handle)
+}
+
+#[cfg(feature = "webapi")]
+#[tokio::test]
+async fn test_webapi_errors_hide_key() {
+    use steamid_ng::webapi::*;
+
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    let api = |base_url: &str| WebApi::with_client(client.clone(), "SECRETKEY").base_url(base_url);
+    let assert_hidden = |e: WebApiError| {
+        assert!(matches!(e, WebApiError::Http(_)));
+        assert!(!e.to_string().contains("SECRETKEY"), "{}", e);
+        assert!(!format!("{:?}", e).contains("SECRETKEY"), "{:?}", e);
+    };
+
+    let (base_url, _) = serve_once("not json").await;
+    assert_hidden(
+        api(&base_url)
+            .resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap_err(),
+    );
+    assert_hidden(
+        api("http://127.0.0.1:9")
+            .resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap_err(),
+    );
+    assert!(!format!("{:?}", api("http://127.0.0.1:9")).contains("SECRETKEY"));
+}
+
+#[cfg(feature = "webapi")]
+#[tokio::test]
+async fn test_webapi_key_failover() {
+    use steamid_ng::webapi::*;
+
+    const FOUND: &str = r#"{"response":{"steamid":"76561197960287930","success":1}}"#;
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    let api = |base_url: &str| {
+        WebApi::with_client(client.clone(), "FIRST")
+            .add_key("SECOND")
+            .base_url(base_url)
+    };
+    let key = |request: &str| {
+        request
+            .split(['?', '&', ' '])
+            .find_map(|param| param.strip_prefix("key="))
+            .unwrap()
+            .to_owned()
+    };
+
+    // A rate limited key is retried with the next one, which is then kept
+    let (base_url, requests) = serve(vec![(429, ""), (200, FOUND), (200, FOUND)]).await;
+    let api = api(&base_url);
+    let gabe = SteamID::from(76561197960287930);
+    assert_eq!(
+        api.resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap(),
+        gabe
+    );
+    assert_eq!(
+        api.clone()
+            .resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap(),
+        gabe
+    );
+    let keys: Vec<_> = requests.await.unwrap().iter().map(|r| key(r)).collect();
+    assert_eq!(keys, ["FIRST", "SECOND", "SECOND"]);
+
+    // Once every key has been refused, the last refusal is returned
+    let (base_url, requests) = serve(vec![(403, ""), (403, "")]).await;
+    let err = WebApi::with_client(client.clone(), "FIRST")
+        .add_key("SECOND")
+        .base_url(base_url)
+        .get_user_group_list(gabe)
+        .await
+        .unwrap_err();
+    match err {
+        WebApiError::Http(e) => assert_eq!(e.status(), Some(reqwest::StatusCode::FORBIDDEN)),
+        e => panic!("{:?}", e),
+    }
+    let keys: Vec<_> = requests.await.unwrap().iter().map(|r| key(r)).collect();
+    assert_eq!(keys, ["FIRST", "SECOND"]);
+}
+
+#[cfg(feature = "webapi")]
+#[tokio::test]
+async fn test_webapi_builder() {
+    use std::time::Duration;
+    use steamid_ng::webapi::*;
+
+    // The proxy gets the whole URL in its request line
+    let (proxy_url, request) =
+        serve_once(r#"{"response":{"steamid":"76561197960287930","success":1}}"#).await;
+    let api = WebApi::builder()
+        .proxy(Proxy::http(proxy_url).unwrap())
+        .build("KEY")
+        .unwrap()
+        .base_url("http://steam.invalid");
+    assert_eq!(
+        api.resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap(),
+        SteamID::from(76561197960287930)
+    );
+    assert_eq!(
+        request.await.unwrap(),
+        "GET http://steam.invalid/ISteamUser/ResolveVanityURL/v1/?key=KEY&vanityurl=gabelogannewell&url_type=1 HTTP/1.1"
+    );
+
+    // A server that accepts the connection but never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let _server = tokio::spawn(async move {
+        let _socket = listener.accept().await.unwrap();
+        std::future::pending::<()>().await
+    });
+    let api = WebApi::builder()
+        .no_proxy()
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_millis(100))
+        .build("KEY")
+        .unwrap()
+        .base_url(base_url);
+    match api
+        .resolve_vanity("gabelogannewell", VanityType::Individual)
+        .await
+    {
+        Err(WebApiError::Http(e)) => assert!(e.is_timeout(), "{:?}", e),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[cfg(feature = "webapi")]
+#[tokio::test]
+async fn test_webapi_get_user_group_list() {
+    use steamid_ng::webapi::*;
+
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    let api = |base_url: &str| WebApi::with_client(client.clone(), "KEY").base_url(base_url);
+    let gabe = SteamID::from(76561197960287930);
+
+    let (base_url, request) =
+        serve_once(r#"{"response":{"success":true,"groups":[{"gid":"4"},{"gid":"3397944"}]}}"#)
+            .await;
+    assert_eq!(
+        api(&base_url).get_user_group_list(gabe).await.unwrap(),
+        [ClanID::new(4), ClanID::new(3397944)]
+    );
+    assert_eq!(
+        request.await.unwrap(),
+        "GET /ISteamUser/GetUserGroupList/v1/?key=KEY&steamid=76561197960287930 HTTP/1.1"
+    );
+
+    let (base_url, _) =
+        serve_once(r#"{"response":{"success":false,"error":"Failed to get groups"}}"#).await;
+    assert!(matches!(
+        api(&base_url).get_user_group_list(gabe).await,
+        Err(WebApiError::Api {
+            success: 0,
+            message: Some(message),
+        }) if message == "Failed to get groups"
+    ));
+
+    let (base_url, _) = serve_once(r#"{"response":{"success":true,"groups":[{"gid":"x"}]}}"#).await;
+    assert!(matches!(
+        api(&base_url).get_user_group_list(gabe).await,
+        Err(WebApiError::Http(_))
+    ));
+}
+
+#[cfg(feature = "webapi")]
+#[tokio::test]
+async fn test_webapi_resolve_vanity() {
+    use steamid_ng::webapi::*;
+
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    let api = |base_url: &str| WebApi::with_client(client.clone(), "KEY").base_url(base_url);
+
+    let (base_url, request) =
+        serve_once(r#"{"response":{"steamid":"76561197960287930","success":1}}"#).await;
+    assert_eq!(
+        api(&base_url)
+            .resolve_vanity("gabelogannewell", VanityType::Individual)
+            .await
+            .unwrap(),
+        SteamID::from(76561197960287930)
+    );
+    assert_eq!(
+        request.await.unwrap(),
+        "GET /ISteamUser/ResolveVanityURL/v1/?key=KEY&vanityurl=gabelogannewell&url_type=1 HTTP/1.1"
+    );
+
+    let (base_url, request) =
+        serve_once(r#"{"response":{"steamid":"103582791429521412","success":1}}"#).await;
+    assert_eq!(
+        api(&base_url)
+            .resolve_community_url("https://steamcommunity.com/groups/Valve/members")
+            .await
+            .unwrap(),
+        SteamID::from(103582791429521412)
+    );
+    assert!(request
+        .await
+        .unwrap()
+        .contains("vanityurl=Valve&url_type=2 "));
+
+    let (base_url, _) = serve_once(r#"{"response":{"success":42,"message":"No match"}}"#).await;
+    assert!(matches!(
+        api(&base_url)
+            .resolve_vanity("nobody", VanityType::Individual)
+            .await,
+        Err(WebApiError::NoMatch)
+    ));
+
+    // URLs that carry the ID don't need a request at all
+    let offline = api("http://127.0.0.1:9");
+    assert_eq!(
+        offline
+            .resolve_community_url("https://steamcommunity.com/profiles/76561197960287930")
+            .await
+            .unwrap(),
+        SteamID::from(76561197960287930)
+    );
+    assert!(matches!(
+        offline
+            .resolve_community_url("https://steamcommunity.com/id/")
+            .await,
+        Err(WebApiError::InvalidUrl(_))
+    ));
+    assert!(matches!(
+        offline
+            .resolve_community_url("https://example.com/id/x")
+            .await,
+        Err(WebApiError::InvalidUrl(_))
+    ));
+}
+
+// Knows a single name, and counts how often it's asked
+#[derive(Default)]
+struct CountingResolver {
+    lookups: std::sync::atomic::AtomicUsize,
+}
+
+impl CountingResolver {
+    fn lookup(&self, vanity: &str) -> Option<SteamID> {
+        self.lookups
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        if vanity.eq_ignore_ascii_case("gabelogannewell") {
+            Some(SteamID::from(76561197960287930))
+        } else {
+            None
+        }
+    }
+
+    fn lookups(&self) -> usize {
+        self.lookups.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+impl vanity::BlockingVanityResolver for CountingResolver {
+    type Error = std::convert::Infallible;
+
+    fn resolve_blocking(
+        &self,
+        vanity: &str,
+        _: vanity::VanityType,
+    ) -> Result<Option<SteamID>, Self::Error> {
+        Ok(self.lookup(vanity))
+    }
+}
+
+impl vanity::VanityResolver for CountingResolver {
+    type Error = std::convert::Infallible;
+
+    fn resolve<'a>(
+        &'a self,
+        vanity: &'a str,
+        _: vanity::VanityType,
+    ) -> vanity::ResolveFuture<'a, Self::Error> {
+        Box::pin(async move { Ok(self.lookup(vanity)) })
+    }
+}
+
+#[test]
+fn test_vanity_resolver() {
+    use std::time::Duration;
+    use steamid_ng::vanity::*;
+
+    let gabe = SteamID::from(76561197960287930);
+    let cached = CachedResolver::new(CountingResolver::default(), Duration::from_secs(60));
+    assert_eq!(
+        cached.resolve_blocking("gabelogannewell", VanityType::Individual),
+        Ok(Some(gabe))
+    );
+    assert_eq!(
+        cached.resolve_blocking("GabeLoganNewell", VanityType::Individual),
+        Ok(Some(gabe))
+    );
+    assert_eq!(
+        cached.resolve_blocking("nobody", VanityType::Individual),
+        Ok(None)
+    );
+    assert_eq!(
+        cached.resolve_blocking("nobody", VanityType::Individual),
+        Ok(None)
+    );
+    assert_eq!(cached.inner().lookups(), 2);
+    // Names are only shared within a vanity type
+    assert_eq!(
+        cached.resolve_blocking("nobody", VanityType::Group),
+        Ok(None)
+    );
+    assert_eq!(cached.inner().lookups(), 3);
+    cached.clear();
+    assert_eq!(
+        cached.resolve_blocking("nobody", VanityType::Group),
+        Ok(None)
+    );
+    assert_eq!(cached.inner().lookups(), 4);
+
+    let uncached = CachedResolver::new(CountingResolver::default(), Duration::ZERO);
+    uncached
+        .resolve_blocking("nobody", VanityType::Individual)
+        .unwrap();
+    uncached.purge_expired();
+    uncached
+        .resolve_blocking("nobody", VanityType::Individual)
+        .unwrap();
+    assert_eq!(uncached.inner().lookups(), 2);
+
+    let resolver = CountingResolver::default();
+    assert_eq!(
+        resolve_community_url_blocking(&resolver, "steamcommunity.com/id/gabelogannewell/"),
+        Ok(gabe)
+    );
+    assert_eq!(
+        resolve_community_url_blocking(
+            &resolver,
+            "https://steamcommunity.com/profiles/76561197960287930"
+        ),
+        Ok(gabe)
+    );
+    assert_eq!(resolver.lookups(), 1);
+    assert!(matches!(
+        resolve_community_url_blocking(&resolver, "https://steamcommunity.com/groups/nobody"),
+        Err(ResolveError::NoMatch)
+    ));
+    assert!(matches!(
+        resolve_community_url_blocking(&resolver, "https://steamcommunity.com/id/"),
+        Err(ResolveError::InvalidUrl(_))
+    ));
+    assert!(matches!(
+        resolve_community_url_blocking(&resolver, "https://example.com/id/gabelogannewell"),
+        Err(ResolveError::InvalidUrl(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_vanity_resolver_async() {
+    use std::time::Duration;
+    use steamid_ng::vanity::*;
+
+    let cached = CachedResolver::new(CountingResolver::default(), Duration::from_secs(60));
+    for _ in 0..2 {
+        assert_eq!(
+            resolve_community_url(&cached, "https://steamcommunity.com/id/GabeLoganNewell")
+                .await
+                .unwrap(),
+            SteamID::from(76561197960287930)
+        );
+    }
+    assert_eq!(cached.inner().lookups(), 1);
 }